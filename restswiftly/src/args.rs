@@ -1,10 +1,34 @@
 use clap::Parser;
 
+use crate::config::AccessLevel;
+
 #[derive(Parser)]
 #[clap(version)]
 pub struct Args {
+    /// Swift files, directories (searched recursively) or glob patterns to read services from
+    #[clap(short, long, value_parser, multiple_values = true)]
+    pub file_name: Vec<String>,
+    /// File all services are generated into, `out.swift` by default
+    #[clap(short, long, value_parser, conflicts_with = "output-dir")]
+    pub output: Option<String>,
+    /// Directory to generate a `<Name>Impl.swift` file for every service into, mirroring the
+    /// layout of the inputs
+    #[clap(short = 'd', long, value_parser)]
+    pub output_dir: Option<String>,
+    /// Only compare generated code with the files on disk, printing differences and failing if
+    /// any of them is out of date
+    #[clap(long, value_parser)]
+    pub check: bool,
+    /// Configuration file, `restswiftly.toml` in the working directory is used if present
     #[clap(short, long, value_parser)]
-    pub file_name: String,
-    #[clap(short, long, value_parser, default_value = "out.swift")]
-    pub output: String,
+    pub config: Option<String>,
+    /// Appended to protocol names to name generated classes, `Impl` by default
+    #[clap(long, value_parser)]
+    pub suffix: Option<String>,
+    /// Access level of generated classes and their members
+    #[clap(long, value_enum)]
+    pub access: Option<AccessLevel>,
+    /// Number of spaces to indent generated code with
+    #[clap(long, value_parser)]
+    pub indent: Option<u8>,
 }
@@ -0,0 +1,126 @@
+use std::{collections::BTreeMap, fs, io::ErrorKind, path::Path};
+
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Deserialize;
+use swift_generator::AccessModifier;
+use thiserror::Error;
+
+use crate::{args::Args, generator::Settings};
+
+/// Configuration read from the working directory when `--config` isn't passed
+pub const CONFIG_FILE: &str = "restswiftly.toml";
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("Invalid configuration {0}: {1}")]
+    Invalid(String, toml::de::Error),
+    #[error("No inputs, pass them with --file-name or set inputs in {CONFIG_FILE}")]
+    NoInputs,
+    #[error("Only one of output and output-dir can be configured")]
+    ConflictingOutputs,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum AccessLevel {
+    Public,
+    Internal,
+    Fileprivate,
+}
+
+impl From<AccessLevel> for AccessModifier {
+    fn from(access: AccessLevel) -> Self {
+        match access {
+            AccessLevel::Public => AccessModifier::Public,
+            AccessLevel::Internal => AccessModifier::Internal,
+            AccessLevel::Fileprivate => AccessModifier::FilePrivate,
+        }
+    }
+}
+
+/// Contents of `restswiftly.toml`, every value is optional
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    pub inputs: Vec<String>,
+    pub output: Option<String>,
+    pub output_dir: Option<String>,
+    pub suffix: Option<String>,
+    pub access: Option<AccessLevel>,
+    pub indent: Option<u8>,
+    pub encoder: Option<String>,
+    pub decoder: Option<String>,
+    pub session: Option<String>,
+    /// Headers added to every request
+    pub headers: BTreeMap<String, String>,
+}
+
+impl Config {
+    /// Reads the configuration from `path`, or from `restswiftly.toml` in the working directory if
+    /// there is one
+    pub fn load(path: Option<&str>) -> Result<Config> {
+        let (path, required) = match path {
+            Some(path) => (path, true),
+            None => (CONFIG_FILE, false),
+        };
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(error) if !required && error.kind() == ErrorKind::NotFound => {
+                return Ok(Config::default())
+            }
+            Err(error) => return Err(error.into()),
+        };
+
+        let mut config: Config =
+            toml::from_str(&content).map_err(|e| ConfigError::Invalid(path.into(), e))?;
+        if config.output.is_some() && config.output_dir.is_some() {
+            return Err(ConfigError::ConflictingOutputs.into());
+        }
+
+        // Paths in the file are relative to it, not to the working directory
+        if let Some(directory) = Path::new(path).parent() {
+            let resolve = |p: &String| directory.join(p).to_string_lossy().into_owned();
+            config.inputs = config.inputs.iter().map(resolve).collect();
+            config.output = config.output.as_ref().map(resolve);
+            config.output_dir = config.output_dir.as_ref().map(resolve);
+        }
+        Ok(config)
+    }
+
+    /// Applies the command line on top of the configuration, arguments take precedence
+    pub fn merge(mut self, args: &Args) -> Result<Config> {
+        if !args.file_name.is_empty() {
+            self.inputs = args.file_name.clone();
+        }
+        if self.inputs.is_empty() {
+            return Err(ConfigError::NoInputs.into());
+        }
+        if args.output.is_some() || args.output_dir.is_some() {
+            self.output = args.output.clone();
+            self.output_dir = args.output_dir.clone();
+        }
+        if args.suffix.is_some() {
+            self.suffix = args.suffix.clone();
+        }
+        if args.access.is_some() {
+            self.access = args.access;
+        }
+        if args.indent.is_some() {
+            self.indent = args.indent;
+        }
+        Ok(self)
+    }
+
+    pub fn settings(&self) -> Settings {
+        let defaults = Settings::default();
+        Settings {
+            suffix: self.suffix.clone().unwrap_or(defaults.suffix),
+            access: self.access.map(AccessModifier::from),
+            encoder: self.encoder.clone().unwrap_or(defaults.encoder),
+            decoder: self.decoder.clone().unwrap_or(defaults.decoder),
+            session: self.session.clone().unwrap_or(defaults.session),
+            headers: self.headers.clone().into_iter().collect(),
+        }
+    }
+}
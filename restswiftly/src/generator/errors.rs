@@ -1,11 +1,31 @@
+use swift_parser::Span;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum GeneratingError {
     #[error("General error: {0}")]
-    GeneralError(String),
+    GeneralError(String, Span),
     #[error("Missing parameter: {0}")]
-    MissingParameter(String),
+    MissingParameter(String, Span),
     #[error("There were unused parameters: {0:?}")]
-    UnusedParameters(Vec<String>),
+    UnusedParameters(Vec<String>, Span),
+    #[error("{0} does not conform to {1}")]
+    MissingConformance(String, String, Span),
+    #[error("{0} cannot be form encoded: {1}")]
+    NotFormEncodable(String, String, Span),
+    #[error("{0} cannot be converted to a string")]
+    NotStringConvertible(String, Span),
+}
+
+impl GeneratingError {
+    pub fn span(&self) -> &Span {
+        match self {
+            GeneratingError::GeneralError(_, span)
+            | GeneratingError::MissingParameter(_, span)
+            | GeneratingError::UnusedParameters(_, span)
+            | GeneratingError::MissingConformance(_, _, span)
+            | GeneratingError::NotFormEncodable(_, _, span)
+            | GeneratingError::NotStringConvertible(_, span) => span,
+        }
+    }
 }
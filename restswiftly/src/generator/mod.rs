@@ -1,23 +1,40 @@
 use anyhow::Result;
-use std::mem;
+use std::{
+    collections::{HashMap, HashSet},
+    mem,
+};
 use swift_generator::{
-    AccessModifier, ClassBuilder, CodeBuilder, ControlType, FieldBuilder, FunctionBuilder,
-    ParameterBuilder,
+    escape_identifier, string_literal, AccessModifier, ClassBuilder, CodeBuilder, ControlType,
+    FieldBuilder, FunctionBuilder, ParameterBuilder, StringLiteral,
 };
-use swift_parser::{Definition, Parameter, PostfixModifier};
+use swift_parser::{Definition, Parameter, PostfixModifier, Span, TypeRef};
 
-use self::errors::GeneratingError;
+pub use self::errors::GeneratingError;
+use self::models::Models;
+use self::parts::Part;
+use self::query::QueryItem;
+use self::responses::Responses;
+pub use self::settings::Settings;
+use self::template::UriTemplate;
 
 mod errors;
+mod models;
 mod parameters;
-mod path;
+mod parts;
 mod query;
+mod responses;
+mod settings;
+mod template;
 #[cfg(test)]
 mod test;
 
 pub struct Generator {
     calls: Vec<FunctionBuilder>,
     definition: Option<CallDefinition>,
+    models: Models,
+    settings: Settings,
+    /// Response handling of calls that don't declare their own
+    responses: Responses,
 }
 
 impl Generator {
@@ -25,9 +42,23 @@ impl Generator {
         Generator {
             calls: vec![],
             definition: None,
+            models: Models::default(),
+            settings: Settings::default(),
+            responses: Responses::default(),
         }
     }
 
+    pub fn set_settings(&mut self, settings: Settings) -> &mut Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Makes the models among `definitions` known, so bodies and responses can be checked
+    pub fn add_models(&mut self, definitions: &[Definition]) -> &mut Self {
+        self.models.add(definitions);
+        self
+    }
+
     pub fn generate_service(
         &mut self,
         name: &str,
@@ -36,11 +67,24 @@ impl Generator {
         for definition in definitions {
             self.generate_definition(definition)?;
         }
-        if !matches!(self.definition, None) {
-            return Err(GeneratingError::GeneralError("Not all tokens were handled".into()).into());
+        if let Some(definition) = &self.definition {
+            return Err(GeneratingError::GeneralError(
+                "Not all tokens were handled".into(),
+                definition.span.clone(),
+            )
+            .into());
         }
 
-        let mut class = ClassBuilder::new(&(name.to_owned() + "Impl"));
+        let mut constructor = make_constructor();
+        let mut calls = mem::take(&mut self.calls);
+        let mut class = ClassBuilder::new(&(name.to_owned() + &self.settings.suffix));
+        if let Some(access) = self.settings.access {
+            class.set_modifier(access);
+            constructor.set_modifier(access);
+            for call in &mut calls {
+                call.set_modifier(access);
+            }
+        }
         class
             .add_super(name)
             .add_field(FieldBuilder {
@@ -53,37 +97,68 @@ impl Generator {
                 name: "interceptors".into(),
                 field_type: "[Interceptor]".into(),
             })
-            .add_function(make_constructor())
-            .add_functions(mem::take(&mut self.calls));
+            .add_function(constructor)
+            .add_functions(calls);
 
         Ok(class)
     }
 
     fn generate_definition(&mut self, definition: &Definition) -> Result<()> {
         match definition {
-            Definition::Comment(comment) => self.handle_call_definition(comment)?,
+            Definition::Comment(comment, span) => self.handle_call_definition(comment, span)?,
             Definition::Function {
                 name,
                 parameters,
                 modifiers,
                 return_type,
-            } => self.generate_function_definition(name, parameters, modifiers, return_type)?,
+                documentation,
+                span,
+            } => self.generate_function_definition(
+                name,
+                parameters,
+                modifiers,
+                return_type,
+                documentation,
+                span,
+            )?,
             value => {
-                return Err(GeneratingError::GeneralError(format!(
-                    "Unsupported definition: {value:?}"
-                ))
+                return Err(GeneratingError::GeneralError(
+                    format!("Unsupported definition: {value:?}"),
+                    value.span().clone(),
+                )
                 .into())
             }
         }
         Ok(())
     }
 
-    fn handle_call_definition(&mut self, comment: &str) -> Result<()> {
+    fn handle_call_definition(&mut self, comment: &str, span: &Span) -> Result<()> {
         let definition = mem::take(&mut self.definition);
-        let definition = if let Some(definition) = definition {
-            parse_headers(definition, comment)?
-        } else {
-            parameters::parse_call_definition(comment)?
+        let definition = match definition {
+            Some(mut definition) => {
+                if definition.responses.parse(comment, span)? {
+                    definition.lines.push((comment.to_owned(), span.clone()));
+                    definition
+                } else if parameters::parse_body(&mut definition, comment, span)?
+                    || parts::parse_part(&mut definition, comment, span)?
+                {
+                    definition
+                } else {
+                    parse_headers(definition, comment, span)?
+                }
+            }
+            // Outside of a call the lines apply to the whole service
+            None if self.responses.parse(comment, span)? => {
+                if !self.calls.is_empty() {
+                    return Err(GeneratingError::GeneralError(
+                        "Responses of the service must be declared before its calls".into(),
+                        span.clone(),
+                    )
+                    .into());
+                }
+                return Ok(());
+            }
+            None => parameters::parse_call_definition(comment, span)?,
         };
         self.definition = Some(definition);
         Ok(())
@@ -94,18 +169,26 @@ impl Generator {
         name: &str,
         parameters: &[Parameter],
         modifiers: &[PostfixModifier],
-        return_type: &Option<String>,
+        return_type: &Option<TypeRef>,
+        documentation: &[String],
+        span: &Span,
     ) -> Result<()> {
         let definition = mem::take(&mut self.definition);
         let definition = match definition {
             Some(definition) => definition,
             None => {
-                return Err(
-                    GeneratingError::GeneralError("No call definition for function".into()).into(),
+                return Err(GeneratingError::GeneralError(
+                    "No call definition for function".into(),
+                    span.clone(),
                 )
+                .into())
             }
         };
-        let call = self.generate_call(name, parameters, modifiers, return_type, definition)?;
+        let mut call =
+            self.generate_call(name, parameters, modifiers, return_type, definition, span)?;
+        for line in documentation {
+            call.add_documentation(line);
+        }
         self.calls.push(call);
         Ok(())
     }
@@ -115,22 +198,86 @@ impl Generator {
         name: &str,
         parameters: &[Parameter],
         modifiers: &[PostfixModifier],
-        return_type: &Option<String>,
-        definition: CallDefinition,
+        return_type: &Option<TypeRef>,
+        mut definition: CallDefinition,
+        span: &Span,
     ) -> Result<FunctionBuilder> {
-        parameters::ensure_present(parameters, &definition)?;
+        parameters::ensure_present(parameters, &definition, span)?;
 
         if !modifiers.contains(&PostfixModifier::Async)
             || !modifiers.contains(&PostfixModifier::Throws)
         {
             return Err(GeneratingError::GeneralError(
                 "Only async throws supported at this time".into(),
+                span.clone(),
             )
             .into());
         }
 
-        let mut failure = CodeBuilder::default();
-        failure.add_statement(r#"fatalError("Unable to fetch data")"#);
+        let body = find_body(&definition, parameters);
+        if !definition.parts.is_empty() {
+            parts::ensure_content_type(&mut definition)?;
+        }
+        let objects: HashSet<_> = definition
+            .query
+            .iter()
+            .filter_map(|item| match &item.value {
+                ParameterValue::Object(name) => Some(name.as_str()),
+                _ => None,
+            })
+            .collect();
+        let mut values = Values::default();
+        for parameter in parameters {
+            if body.map(|b| b.name == parameter.name) == Some(true)
+                || definition
+                    .parts
+                    .iter()
+                    .any(|p| p.parameter == parameter.name)
+            {
+                continue;
+            }
+            let name = parameter.name.as_str();
+            let mut type_ref = &parameter.parameter_type;
+            if let TypeRef::Optional(inner) = type_ref {
+                values.optional.insert(name);
+                type_ref = inner;
+            }
+            if objects.contains(name) {
+                if !matches!(
+                    type_ref,
+                    TypeRef::Named(_) | TypeRef::Opaque(_) | TypeRef::Existential(_)
+                ) {
+                    return Err(GeneratingError::GeneralError(
+                        format!("{type_ref} cannot be expanded into query items, only models can"),
+                        parameter.span.clone(),
+                    )
+                    .into());
+                }
+                self.models.ensure_encodable(type_ref, &parameter.span)?;
+            } else if let TypeRef::Dictionary(key, value) = type_ref {
+                if key.as_ref() != &TypeRef::named("String") {
+                    return Err(GeneratingError::GeneralError(
+                        format!("Dictionary keys must be strings, found {key}"),
+                        parameter.span.clone(),
+                    )
+                    .into());
+                }
+                let value = self.models.string_value("value", value, &parameter.span)?;
+                values.entries.insert(name, value);
+            } else if let TypeRef::Array(element) = type_ref {
+                let element = self
+                    .models
+                    .string_value("element", element, &parameter.span)?;
+                values.elements.insert(name, element);
+            } else {
+                let value = self.models.string_value(
+                    &escape_identifier(name),
+                    type_ref,
+                    &parameter.span,
+                )?;
+                values.values.insert(name, value);
+            }
+        }
 
         let mut code = CodeBuilder::default();
         code.add_statement(&format!(
@@ -140,31 +287,101 @@ impl Generator {
             } else {
                 "var"
             },
-            path::create_template(&definition),
+            definition.path.expand(&values),
         ));
         if !definition.query.is_empty() {
-            query::add_parameters(&mut code, definition.query);
+            query::add_parameters(&mut code, &definition.query, &values);
         }
         code.add_statement("var request = URLRequest(url: url)")
-            .add_statement(&format!(r#"request.httpMethod = "{}""#, definition.verb));
-        add_headers(&mut code, &definition.headers);
-        if has_body(&definition.verb, parameters) {
-            let encoder = select_encoder(&definition.headers)?;
-            code.add_statement(&format!("let encoder = {encoder}"))
-                .add_statement("request.httpBody = try encoder.encode(body)");
-        }
-        code.add_statement(
-            "let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }",
-        )
-        .add_statement("let (data, response) = try await chain.proceed(with: request)")
-        .add_control(
-            ControlType::Guard,
-            "(response as? HTTPURLResponse)?.statusCode == 200",
-            failure,
+            .add_statement(&format!(
+                "request.httpMethod = {}",
+                string_literal(&definition.verb)
+            ));
+        add_headers(
+            &mut code,
+            &self.settings.headers,
+            &definition.headers,
+            &values,
         );
+        if !definition.parts.is_empty() {
+            parts::add_parts(
+                &mut code,
+                &definition.parts,
+                parameters,
+                &self.models,
+                &self.settings.encoder,
+            )?;
+        }
+        let mut exchange = "data(for: request)".to_owned();
+        if let Some(body) = body {
+            let name = escape_identifier(&body.name);
+            let (optional, type_ref) = match &body.parameter_type {
+                TypeRef::Optional(inner) => (true, inner.as_ref()),
+                type_ref => (false, type_ref),
+            };
+            match RawBody::of(type_ref) {
+                Some(raw) => {
+                    self.add_content_type(&mut code, &definition)?;
+                    let statement = match raw {
+                        RawBody::Data => Some(format!("request.httpBody = {name}")),
+                        RawBody::String => Some(format!("request.httpBody = Data({name}.utf8)")),
+                        // Files are streamed by the session instead of being read into the request
+                        RawBody::File if optional => {
+                            return Err(GeneratingError::GeneralError(
+                                "File bodies can't be optional".into(),
+                                body.span.clone(),
+                            )
+                            .into())
+                        }
+                        RawBody::File => {
+                            exchange = format!("upload(for: request, fromFile: {name})");
+                            None
+                        }
+                    };
+                    match statement {
+                        // The request is sent without a body when nil
+                        Some(statement) if optional => {
+                            let mut add = CodeBuilder::default();
+                            add.add_statement(&statement);
+                            code.add_control(ControlType::If, &format!("let {name} = {name}"), add);
+                        }
+                        Some(statement) => {
+                            code.add_statement(&statement);
+                        }
+                        None => (),
+                    }
+                }
+                None => {
+                    let encoding = select_encoding(&definition)?;
+                    self.models
+                        .ensure_encodable(&body.parameter_type, &body.span)?;
+                    if encoding == BodyEncoding::Form {
+                        self.models
+                            .ensure_form_encodable(&body.parameter_type, &body.span)?;
+                    }
+                    let encoder = match encoding {
+                        BodyEncoding::Json => self.settings.encoder.as_str(),
+                        _ => encoding.encoder(),
+                    };
+                    code.add_statement(&format!("let encoder = {encoder}"))
+                        .add_statement(&format!("request.httpBody = try encoder.encode({name})"));
+                }
+            }
+        }
+        code.add_statement(&format!(
+            "let chain = Chain(using: interceptors) {{ {}.{exchange} }}",
+            self.settings.session
+        ))
+        .add_statement("let (data, response) = try await chain.proceed(with: request)");
+        let responses = definition.responses.or(&self.responses);
+        if let Some((error, span)) = &responses.error {
+            self.models.ensure_decodable(error, span)?;
+        }
+        responses.add_check(&mut code, &self.settings.decoder);
 
         if let Some(return_type) = return_type {
-            code.add_statement("let decoder = JSONDecoder()")
+            self.models.ensure_decodable(return_type, span)?;
+            code.add_statement(&format!("let decoder = {}", self.settings.decoder))
                 .add_statement(&format!(
                     "return try decoder.decode({return_type}.self, from: data)"
                 ));
@@ -179,20 +396,53 @@ impl Generator {
             .map(|p| ParameterBuilder {
                 label: p.label.as_ref().cloned(),
                 name: p.name.clone(),
-                parameter_type: p.parameter_type.clone(),
+                parameter_type: p.parameter_type.to_string(),
             })
             .for_each(|p| {
                 function.add_parameter(p);
             });
         function.set_async(true).set_throws(true);
         if let Some(return_type) = return_type {
-            function.set_return_type(return_type);
+            function.set_return_type(&return_type.to_string());
         }
         function.add_code(code);
         Ok(function)
     }
 }
 
+impl Generator {
+    /// Raw bodies are sent as `application/octet-stream`, unless the call or the settings declare a content type
+    fn add_content_type(&self, code: &mut CodeBuilder, definition: &CallDefinition) -> Result<()> {
+        let content_type = definition
+            .headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-type"));
+        if let Some((name, HeaderValue::Value(value))) = content_type {
+            if value == "multipart/form-data" {
+                return Err(GeneratingError::GeneralError(
+                    "Raw bodies can't be sent as multipart/form-data".into(),
+                    definition.locate(name),
+                )
+                .into());
+            }
+        }
+        let declared = content_type.is_some()
+            || self
+                .settings
+                .headers
+                .iter()
+                .any(|(name, _)| name.eq_ignore_ascii_case("content-type"));
+        if !declared {
+            code.add_statement(&format!(
+                "request.addValue({}, forHTTPHeaderField: {})",
+                string_literal("application/octet-stream"),
+                string_literal("Content-Type")
+            ));
+        }
+        Ok(())
+    }
+}
+
 fn make_constructor() -> FunctionBuilder {
     let mut trim = CodeBuilder::default();
     trim.add_statement("baseUrl = String(baseUrl.removeLast())");
@@ -220,7 +470,12 @@ fn make_constructor() -> FunctionBuilder {
     constructor
 }
 
-fn parse_headers(mut definition: CallDefinition, header: &str) -> Result<CallDefinition> {
+fn parse_headers(
+    mut definition: CallDefinition,
+    header: &str,
+    span: &Span,
+) -> Result<CallDefinition> {
+    definition.lines.push((header.to_owned(), span.clone()));
     let mut parts = header.splitn(2, ": ");
     let name = match parts.next() {
         None => return Ok(definition),
@@ -230,87 +485,282 @@ fn parse_headers(mut definition: CallDefinition, header: &str) -> Result<CallDef
         None => "".to_owned(),
         Some(value) => value.to_owned(),
     };
-    if value.starts_with('{') && value.ends_with('}') {
-        definition.headers.push((
-            name,
-            ParameterValue::Parameter(value[1..value.len() - 1].to_string()),
-        ));
-    } else {
+    if let Some(map) = value.strip_prefix('{').and_then(|v| v.strip_suffix("*}")) {
+        if name != "Headers" {
+            return Err(GeneratingError::GeneralError(
+                "Header maps must be declared as Headers: {name*}".into(),
+                definition.locate(&value),
+            )
+            .into());
+        }
         definition
             .headers
-            .push((name, ParameterValue::Value(value)));
+            .push((name, HeaderValue::Map(map.to_owned())));
+    } else {
+        let value = parse_interpolation(&value, |text| definition.locate(text))?;
+        definition.headers.push((name, value));
     }
 
     Ok(definition)
 }
 
-fn add_headers(code: &mut CodeBuilder, headers: &Vec<(String, ParameterValue)>) {
+/// Parses a header value such as `Bearer {token}`, a value that is only a placeholder is used as is
+fn parse_interpolation(value: &str, locate: impl Fn(&str) -> Span) -> Result<HeaderValue> {
+    let mut segments = vec![];
+    let mut rest = value;
+    while let Some(start) = rest.find('{') {
+        if start > 0 {
+            segments.push(Segment::Literal(rest[..start].to_owned()));
+        }
+        rest = &rest[start..];
+        let end = rest.find('}').ok_or_else(|| {
+            GeneratingError::GeneralError("Unclosed placeholder in header".into(), locate(rest))
+        })?;
+        let name = &rest[1..end];
+        if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            return Err(GeneratingError::GeneralError(
+                format!("Invalid parameter name in header: {name}"),
+                locate(&rest[..=end]),
+            )
+            .into());
+        }
+        segments.push(Segment::Parameter(name.to_owned()));
+        rest = &rest[end + 1..];
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Literal(rest.to_owned()));
+    }
+
+    Ok(match segments.as_slice() {
+        [] => HeaderValue::Value(String::new()),
+        [Segment::Literal(literal)] => HeaderValue::Value(literal.clone()),
+        [Segment::Parameter(name)] => HeaderValue::Parameter(name.clone()),
+        _ => HeaderValue::Interpolated(segments),
+    })
+}
+
+fn add_headers(
+    code: &mut CodeBuilder,
+    defaults: &[(String, String)],
+    headers: &Vec<(String, HeaderValue)>,
+    values: &Values,
+) {
+    // Headers of the call replace the defaults with the same name
+    for (header, value) in defaults {
+        if !headers.iter().any(|(h, _)| h.eq_ignore_ascii_case(header)) {
+            code.add_statement(&format!(
+                "request.addValue({}, forHTTPHeaderField: {})",
+                string_literal(value),
+                string_literal(header)
+            ));
+        }
+    }
     for (header, value) in headers {
-        let value = match value {
-            ParameterValue::Parameter(name) => name.into(),
-            ParameterValue::Value(value) => format!(r#""{value}""#),
-            ParameterValue::None => r#""""#.to_owned(),
+        let mut value = match value {
+            HeaderValue::Map(name) => {
+                let statement = "request.addValue({value}, forHTTPHeaderField: {key})";
+                if values.is_optional(name) {
+                    let mut entries = CodeBuilder::default();
+                    add_entries(&mut entries, name, values, statement);
+                    let name = escape_identifier(name);
+                    code.add_control(ControlType::If, &format!("let {name} = {name}"), entries);
+                } else {
+                    add_entries(code, name, values, statement);
+                }
+                continue;
+            }
+            HeaderValue::Parameter(name) => values.get(name),
+            HeaderValue::Interpolated(segments) => interpolate(segments, values),
+            HeaderValue::Value(value) => string_literal(value),
         };
-        if header.to_lowercase() == "content-type" && value == r#""multipart/form-data""# {
+        if header.to_lowercase() == "content-type" && value == string_literal("multipart/form-data")
+        {
             code.add_statement("let boundary = UUID().uuidString");
-            code.add_statement(&format!(
-                r#"request.addValue("multipart/form-data; boundary=\(boundary)", forHTTPHeaderField: "{header}")"#
-            ));
-        } else {
-            code.add_statement(&format!(
-                r#"request.addValue({value}, forHTTPHeaderField: "{header}")"#
-            ));
+            value = StringLiteral::new("multipart/form-data; boundary=")
+                .add_interpolation("boundary")
+                .to_string();
+        }
+        code.add_statement(&format!(
+            "request.addValue({value}, forHTTPHeaderField: {})",
+            string_literal(header)
+        ));
+    }
+}
+
+/// Swift string literal of `segments`, with parameters interpolated as strings
+fn interpolate(segments: &[Segment], values: &Values) -> String {
+    let mut literal = StringLiteral::default();
+    for segment in segments {
+        match segment {
+            Segment::Literal(text) => literal.add_text(text),
+            Segment::Parameter(name) => literal.add_interpolation(&values.get(name)),
+        };
+    }
+    literal.to_string()
+}
+
+#[derive(Debug, PartialEq)]
+enum BodyEncoding {
+    Json,
+    Form,
+    Multipart,
+}
+
+impl BodyEncoding {
+    fn encoder(&self) -> &'static str {
+        match self {
+            BodyEncoding::Json => "JSONEncoder()",
+            BodyEncoding::Form => "FormEncoder()",
+            BodyEncoding::Multipart => "MultipartEncoder(boundary: boundary)",
         }
     }
 }
 
-fn select_encoder(headers: &Vec<(String, ParameterValue)>) -> Result<String> {
-    for (name, value) in headers {
+/// Bodies that are sent as they are, without an encoder
+enum RawBody {
+    Data,
+    /// Sent as UTF-8 text
+    String,
+    /// File uploaded from the URL
+    File,
+}
+
+impl RawBody {
+    fn of(type_ref: &TypeRef) -> Option<RawBody> {
+        match type_ref.name()? {
+            "Data" => Some(RawBody::Data),
+            "String" => Some(RawBody::String),
+            "URL" => Some(RawBody::File),
+            _ => None,
+        }
+    }
+}
+
+fn select_encoding(definition: &CallDefinition) -> Result<BodyEncoding> {
+    for (name, value) in &definition.headers {
         if name.to_lowercase() != "content-type" {
             continue;
         }
         match value {
-            ParameterValue::None => {
-                return Err(
-                    GeneratingError::GeneralError("content type must not be empty".into()).into(),
+            HeaderValue::Parameter(parameter) | HeaderValue::Map(parameter) => {
+                return Err(GeneratingError::GeneralError(
+                    "content type must not be variable".into(),
+                    definition.locate(&format!("{{{parameter}}}")),
                 )
+                .into())
             }
-            ParameterValue::Parameter(_) => {
+            HeaderValue::Interpolated(_) => {
                 return Err(GeneratingError::GeneralError(
                     "content type must not be variable".into(),
+                    definition.locate(name),
                 )
                 .into())
             }
-            ParameterValue::Value(value) => {
+            HeaderValue::Value(value) => {
                 return match value.as_str() {
-                    "application/json" => Ok("JSONEncoder()".into()),
-                    "application/x-www-form-urlencoded" => Ok("FormEncoder()".into()),
-                    "multipart/form-data" => Ok("MultipartEncoder(boundary: boundary)".into()),
-                    value => {
-                        Err(GeneratingError::GeneralError(format!("{value} not supported")).into())
-                    }
+                    "" => Err(GeneratingError::GeneralError(
+                        "content type must not be empty".into(),
+                        definition.locate(name),
+                    )
+                    .into()),
+                    "application/json" => Ok(BodyEncoding::Json),
+                    "application/x-www-form-urlencoded" => Ok(BodyEncoding::Form),
+                    "multipart/form-data" => Ok(BodyEncoding::Multipart),
+                    value => Err(GeneratingError::GeneralError(
+                        format!("{value} not supported"),
+                        definition.locate(value),
+                    )
+                    .into()),
                 }
             }
         }
     }
 
-    Ok("JSONEncoder()".into())
+    Ok(BodyEncoding::Json)
 }
 
 struct CallDefinition {
     verb: String,
-    headers: Vec<(String, ParameterValue)>,
-    path: String,
-    path_params: Vec<String>,
-    query: Vec<(String, ParameterValue)>,
+    headers: Vec<(String, HeaderValue)>,
+    path: UriTemplate,
+    query: Vec<QueryItem>,
+    /// Parameter sent as the body, declared with `Body: {name}`
+    body: Option<String>,
+    /// Parameters sent as parts of a multipart body, declared with `Part: name={parameter}`
+    parts: Vec<Part>,
+    /// Overrides the response handling of the service
+    responses: Responses,
+    span: Span,
+    /// Comment lines the definition was read from, used for error locations
+    lines: Vec<(String, Span)>,
+}
+
+impl CallDefinition {
+    /// Span of the first occurrence of `text` in the definition, or the whole call line
+    fn locate(&self, text: &str) -> Span {
+        self.lines
+            .iter()
+            .find_map(|(line, span)| find_span(line, span, text))
+            .unwrap_or_else(|| self.span.clone())
+    }
 }
 
-fn has_body(verb: &str, parameters: &[Parameter]) -> bool {
-    parameters
-        .iter()
-        .map(|p| p.name.clone())
-        .any(|p| p == "body")
-        && matches!(verb, "PATCH" | "POST" | "PUT")
+/// Span of `text` within `line`, which starts at `span`
+fn find_span(line: &str, span: &Span, text: &str) -> Option<Span> {
+    line.find(text)
+        .map(|index| span.offset(line[..index].chars().count(), text.chars().count()))
+}
+
+/// Parameters used in the path, query and headers, converted to strings
+#[derive(Default)]
+struct Values<'a> {
+    values: HashMap<&'a str, String>,
+    /// Parameters with optional types, their values are converted once unwrapped
+    optional: HashSet<&'a str>,
+    /// Conversions of `element` of array parameters
+    elements: HashMap<&'a str, String>,
+    /// Conversions of `value` of dictionary parameters
+    entries: HashMap<&'a str, String>,
+}
+
+impl Values<'_> {
+    /// Expression of parameter `name` converted to a string
+    fn get(&self, name: &str) -> String {
+        self.values
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| escape_identifier(name))
+    }
+
+    fn is_optional(&self, name: &str) -> bool {
+        self.optional.contains(name)
+    }
+
+    /// Expression converting `element` to a string if `name` is an array
+    fn element(&self, name: &str) -> Option<&str> {
+        self.elements.get(name).map(String::as_str)
+    }
+
+    /// Expression converting `value` of an entry to a string if `name` is a dictionary
+    fn entry_value(&self, name: &str) -> Option<&str> {
+        self.entries.get(name).map(String::as_str)
+    }
+}
+
+/// Parameter declared with `Body: {name}`, or the one named `body` when the call has no parts
+fn find_body<'a>(
+    definition: &CallDefinition,
+    parameters: &'a [Parameter],
+) -> Option<&'a Parameter> {
+    if !matches!(definition.verb.as_str(), "PATCH" | "POST" | "PUT") {
+        return None;
+    }
+    let name = match &definition.body {
+        Some(name) => name.as_str(),
+        None if definition.parts.is_empty() => "body",
+        None => return None,
+    };
+    parameters.iter().find(|p| p.name == name)
 }
 
 #[derive(Debug)]
@@ -318,4 +768,40 @@ enum ParameterValue {
     None,
     Parameter(String),
     Value(String),
+    /// Dictionary parameter expanded into an item for every entry, `{filters*}`
+    Map(String),
+    /// Encodable parameter expanded into a query item for every field, `{filter}`
+    Object(String),
+}
+
+#[derive(Debug)]
+enum HeaderValue {
+    Parameter(String),
+    Value(String),
+    /// Dictionary parameter expanded into a header for every entry, `Headers: {name*}`
+    Map(String),
+    /// Literal text mixed with parameters, `Bearer {token}`
+    Interpolated(Vec<Segment>),
+}
+
+#[derive(Debug)]
+enum Segment {
+    Literal(String),
+    Parameter(String),
+}
+
+/// Adds `statement` for every entry of dictionary `parameter` in the order of keys, `{key}` and
+/// `{value}` in the statement are replaced with the entry converted to strings
+fn add_entries(code: &mut CodeBuilder, parameter: &str, values: &Values, statement: &str) {
+    let value = values.entry_value(parameter).unwrap_or("value");
+    let mut entry = CodeBuilder::default();
+    entry.add_statement(&statement.replace("{key}", "key").replace("{value}", value));
+    code.add_control(
+        ControlType::For,
+        &format!(
+            "(key, value) in {}.sorted(by: {{ $0.key < $1.key }})",
+            escape_identifier(parameter)
+        ),
+        entry,
+    );
 }
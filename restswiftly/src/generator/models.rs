@@ -0,0 +1,316 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+use log::warn;
+use swift_parser::{Definition, Model, ModelKind, Span, TypeRef};
+
+use super::GeneratingError;
+
+/// Standard library and Foundation types that are `Codable` themselves
+const CODABLE_TYPES: [&str; 19] = [
+    "Bool", "Data", "Date", "Decimal", "Double", "Float", "Int", "Int8", "Int16", "Int32", "Int64",
+    "String", "UInt", "UInt8", "UInt16", "UInt32", "UInt64", "URL", "UUID",
+];
+
+/// Generic types that are `Codable` when their arguments are
+const CODABLE_CONTAINERS: [&str; 4] = ["Array", "Dictionary", "Optional", "Set"];
+
+/// Types that can be converted with `String(value)`
+const NUMERIC_TYPES: [&str; 13] = [
+    "Bool", "Double", "Float", "Int", "Int8", "Int16", "Int32", "Int64", "UInt", "UInt8", "UInt16",
+    "UInt32", "UInt64",
+];
+
+/// Protocols that provide a `description` suitable for a string value
+const DESCRIBABLE_PROTOCOLS: [&str; 2] = ["CustomStringConvertible", "LosslessStringConvertible"];
+
+/// Models declared alongside the services, grouped by name with their extensions
+#[derive(Default)]
+pub(super) struct Models {
+    models: HashMap<String, Vec<Model>>,
+}
+
+impl Models {
+    pub(super) fn add(&mut self, definitions: &[Definition]) {
+        for definition in definitions {
+            if let Definition::Model(model) = definition {
+                self.models
+                    .entry(model.name.clone())
+                    .or_default()
+                    .push(model.clone());
+            }
+        }
+    }
+
+    pub(super) fn ensure_encodable(&self, type_ref: &TypeRef, span: &Span) -> Result<()> {
+        match type_ref {
+            // Encoders accept existentials and opaque types by opening them
+            TypeRef::Opaque(inner) | TypeRef::Existential(inner)
+                if matches!(inner.name(), Some("Encodable" | "Codable")) =>
+            {
+                Ok(())
+            }
+            TypeRef::Opaque(inner) | TypeRef::Existential(inner) => {
+                self.ensure_conforms(inner, "Encodable", span)
+            }
+            type_ref => self.ensure_conforms(type_ref, "Encodable", span),
+        }
+    }
+
+    pub(super) fn ensure_decodable(&self, type_ref: &TypeRef, span: &Span) -> Result<()> {
+        match type_ref {
+            TypeRef::Opaque(_) | TypeRef::Existential(_) => Err(GeneratingError::GeneralError(
+                format!("Return type {type_ref} cannot be decoded"),
+                span.clone(),
+            )
+            .into()),
+            type_ref => self.ensure_conforms(type_ref, "Decodable", span),
+        }
+    }
+
+    /// Checks `type_ref` is a struct or class whose properties can all be written as form fields,
+    /// `FormEncoder` writes nested values as `address[city]` and arrays as `tags[0]`
+    pub(super) fn ensure_form_encodable(&self, type_ref: &TypeRef, span: &Span) -> Result<()> {
+        let not_encodable = |reason: String| {
+            GeneratingError::NotFormEncodable(type_ref.to_string(), reason, span.clone())
+        };
+
+        let name = match type_ref {
+            TypeRef::Named(_) => type_ref.name().unwrap_or_default(),
+            // The concrete type is only known at runtime
+            TypeRef::Opaque(_) | TypeRef::Existential(_) => return Ok(()),
+            _ => return Err(not_encodable("only models can be form encoded".into()).into()),
+        };
+        let models = match self.models.get(name) {
+            // A single value has no field name to be written with
+            Some(models) if models.iter().any(|m| m.kind == ModelKind::Enum) => {
+                return Err(
+                    not_encodable("only structs and classes can be form encoded".into()).into(),
+                )
+            }
+            Some(models) => models,
+            None if CODABLE_TYPES.contains(&name) => {
+                return Err(not_encodable("only models can be form encoded".into()).into())
+            }
+            None => {
+                warn!("Unknown type {type_ref}, assuming it can be form encoded");
+                return Ok(());
+            }
+        };
+
+        let mut checked = HashSet::from([name]);
+        self.ensure_form_fields(models, &mut checked)
+            .map_err(|reason| not_encodable(reason).into())
+    }
+
+    /// Checks every property of `models` is written under its own key, returns the reason if one isn't
+    fn ensure_form_fields<'a>(
+        &'a self,
+        models: &'a [Model],
+        checked: &mut HashSet<&'a str>,
+    ) -> Result<(), String> {
+        for property in models.iter().flat_map(|m| &m.properties) {
+            match &property.property_type {
+                Some(property_type) if !self.is_form_field(property_type, checked) => {
+                    return Err(format!(
+                        "property {} of type {property_type} can't be written as a form field",
+                        property.name
+                    ))
+                }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether values of `type_ref` are written as fields, either as a value or with nested keys
+    fn is_form_field<'a>(&'a self, type_ref: &'a TypeRef, checked: &mut HashSet<&'a str>) -> bool {
+        match type_ref {
+            TypeRef::Optional(inner) | TypeRef::Array(inner) => self.is_form_field(inner, checked),
+            TypeRef::Dictionary(key, value) => {
+                key.name() == Some("String") && self.is_form_field(value, checked)
+            }
+            TypeRef::Named(_) => {
+                let name = type_ref.name().unwrap_or_default();
+                match self.models.get(name) {
+                    // Enums are only written as a value through their raw value
+                    Some(models) if models.iter().any(|m| m.kind == ModelKind::Enum) => {
+                        self.is_plain_value(type_ref)
+                    }
+                    // Recursive models are checked by the first occurrence
+                    Some(_) if !checked.insert(name) => true,
+                    Some(models) => self.ensure_form_fields(models, checked).is_ok(),
+                    // Standard types and types declared elsewhere are written as values
+                    None => true,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether `type_ref` is written as text rather than encoded, strings, numbers and enums with a
+    /// string or numeric raw value
+    pub(super) fn is_plain_value(&self, type_ref: &TypeRef) -> bool {
+        let type_name = match type_ref {
+            TypeRef::Named(components) if components.iter().all(|c| c.generics.is_empty()) => {
+                type_ref.name().unwrap_or_default()
+            }
+            _ => return false,
+        };
+        if matches!(type_name, "String" | "Decimal" | "URL" | "UUID")
+            || NUMERIC_TYPES.contains(&type_name)
+        {
+            return true;
+        }
+        let raw_type = self
+            .models
+            .get(type_name)
+            .and_then(|models| raw_type(models));
+        matches!(raw_type, Some(raw_type) if raw_type == "String" || NUMERIC_TYPES.contains(&raw_type))
+    }
+
+    /// Swift expression converting `name` of `type_ref` to a string, for query, path and header
+    /// values
+    pub(super) fn string_value(
+        &self,
+        name: &str,
+        type_ref: &TypeRef,
+        span: &Span,
+    ) -> Result<String> {
+        let not_convertible =
+            || GeneratingError::NotStringConvertible(type_ref.to_string(), span.clone());
+
+        let type_name = match type_ref {
+            TypeRef::Named(components) if components.iter().all(|c| c.generics.is_empty()) => {
+                type_ref.name().unwrap_or_default()
+            }
+            _ => return Err(not_convertible().into()),
+        };
+        match type_name {
+            "String" => return Ok(name.to_owned()),
+            "Decimal" => return Ok(format!("{name}.description")),
+            "URL" => return Ok(format!("{name}.absoluteString")),
+            "UUID" => return Ok(format!("{name}.uuidString")),
+            type_name if NUMERIC_TYPES.contains(&type_name) => {
+                return Ok(format!("String({name})"))
+            }
+            _ => (),
+        }
+
+        let models = match self.models.get(type_name) {
+            Some(models) => models,
+            None => {
+                warn!("Unknown type {type_ref}, converting it with String(describing:)");
+                return Ok(format!("String(describing: {name})"));
+            }
+        };
+        match raw_type(models) {
+            Some("String") => return Ok(format!("{name}.rawValue")),
+            Some(raw_type) if NUMERIC_TYPES.contains(&raw_type) => {
+                return Ok(format!("String({name}.rawValue)"))
+            }
+            _ => (),
+        }
+        let describable = DESCRIBABLE_PROTOCOLS
+            .iter()
+            .any(|protocol| self.conforms(type_name, protocol, &mut vec![]) == Some(true));
+        if describable {
+            Ok(format!("{name}.description"))
+        } else if self.conforms(type_name, "RawRepresentable", &mut vec![]) == Some(true) {
+            Ok(format!("String(describing: {name}.rawValue)"))
+        } else if models.iter().all(|m| m.kind == ModelKind::Extension) {
+            warn!("Unknown type {type_ref}, converting it with String(describing:)");
+            Ok(format!("String(describing: {name})"))
+        } else {
+            Err(not_convertible().into())
+        }
+    }
+
+    fn ensure_conforms(&self, type_ref: &TypeRef, protocol: &str, span: &Span) -> Result<()> {
+        let missing_conformance = || {
+            GeneratingError::MissingConformance(type_ref.to_string(), protocol.into(), span.clone())
+        };
+
+        match type_ref {
+            TypeRef::Optional(inner) | TypeRef::Array(inner) => {
+                self.ensure_conforms(inner, protocol, span)
+            }
+            TypeRef::Dictionary(key, value) => {
+                self.ensure_conforms(key, protocol, span)?;
+                self.ensure_conforms(value, protocol, span)
+            }
+            TypeRef::Named(components) => {
+                let component = match components.last() {
+                    Some(component) => component,
+                    None => return Ok(()),
+                };
+                let name = component.name.as_str();
+                if CODABLE_TYPES.contains(&name) {
+                    return Ok(());
+                }
+                if !CODABLE_CONTAINERS.contains(&name) {
+                    match self.conforms(name, protocol, &mut vec![]) {
+                        Some(true) => (),
+                        Some(false) => return Err(missing_conformance().into()),
+                        None => {
+                            warn!("Unknown type {type_ref}, assuming it is {protocol}");
+                            return Ok(());
+                        }
+                    }
+                }
+                for generic in &component.generics {
+                    self.ensure_conforms(generic, protocol, span)?;
+                }
+                Ok(())
+            }
+            TypeRef::Tuple(_) | TypeRef::Opaque(_) | TypeRef::Existential(_) => {
+                Err(missing_conformance().into())
+            }
+        }
+    }
+
+    /// Whether the model `name` conforms to `protocol` directly, through `Codable`, one of its
+    /// extensions or a superclass. `None` when the model itself is not known.
+    fn conforms<'a>(
+        &'a self,
+        name: &'a str,
+        protocol: &str,
+        visited: &mut Vec<&'a str>,
+    ) -> Option<bool> {
+        let models = self.models.get(name)?;
+        if visited.contains(&name) {
+            return Some(false);
+        }
+        visited.push(name);
+
+        for conformance in models.iter().flat_map(|m| &m.conformances) {
+            let conformance = match conformance.name() {
+                Some(conformance) => conformance,
+                None => continue,
+            };
+            let codable = matches!(protocol, "Encodable" | "Decodable") && conformance == "Codable";
+            if conformance == protocol
+                || codable
+                || self.conforms(conformance, protocol, visited) == Some(true)
+            {
+                return Some(true);
+            }
+        }
+
+        // Types only extended here are declared elsewhere and could conform there
+        if models.iter().all(|m| m.kind == ModelKind::Extension) {
+            None
+        } else {
+            Some(false)
+        }
+    }
+}
+
+/// Raw value type of an enum, the first type it conforms to
+fn raw_type(models: &[Model]) -> Option<&str> {
+    models
+        .iter()
+        .filter(|m| m.kind == ModelKind::Enum)
+        .find_map(|m| m.conformances.first())
+        .and_then(|c| c.name())
+}
@@ -1,134 +1,363 @@
 use std::collections::HashMap;
 
 use anyhow::Result;
-use swift_parser::Parameter;
+use swift_parser::{Parameter, Span, TypeRef};
 
-use super::{errors::GeneratingError, path, query, CallDefinition, ParameterValue};
+use super::{
+    errors::GeneratingError,
+    find_span, query,
+    responses::Responses,
+    template::{self, UriTemplate},
+    CallDefinition, HeaderValue, ParameterValue, Segment,
+};
 
-pub(super) fn ensure_present(parameters: &[Parameter], definition: &CallDefinition) -> Result<()> {
-    let mut names: HashMap<_, _> = parameters
-        .iter()
-        .map(|p| (p.name.clone(), p.parameter_type.clone()))
-        .collect();
+pub(super) fn ensure_present(
+    parameters: &[Parameter],
+    definition: &CallDefinition,
+    span: &Span,
+) -> Result<()> {
+    let mut names: HashMap<_, _> = parameters.iter().map(|p| (p.name.clone(), p)).collect();
 
-    if names.contains_key("body") {
-        match definition.verb.as_ref() {
-            "PATCH" | "POST" | "PUT" => {
-                names.remove("body");
-            }
-            other => {
-                return Err(GeneratingError::GeneralError(format!(
-                    "{other} does not support sending a body"
-                ))
-                .into());
-            }
+    let body = match &definition.body {
+        Some(name) => Some(remove_param(&mut names, name, || {
+            definition.locate(&format!("{{{name}}}"))
+        })?),
+        None if definition.parts.is_empty() => names.remove("body"),
+        None => None,
+    };
+    if let Some(body) = body {
+        ensure_body_allowed(definition, body)?;
+        if !definition.parts.is_empty() {
+            return Err(GeneratingError::GeneralError(
+                "Parts can't be sent with a body".into(),
+                body.span.clone(),
+            )
+            .into());
         }
     }
+    filter_parts(&mut names, definition)?;
 
-    filter_query(&mut names, &definition.query)?;
-    filter_path(&mut names, &definition.path_params)?;
-    filter_headers(&mut names, &definition.headers)?;
+    // Query, path and header values can share parameters, which are removed only once
+    let mut used = HashMap::new();
+    filter_query(&mut names, &mut used, definition)?;
+    filter_path(&mut names, &mut used, definition)?;
+    filter_headers(&mut names, &mut used, definition)?;
     if !names.is_empty() {
+        let unused: Vec<_> = parameters
+            .iter()
+            .filter(|p| names.contains_key(&p.name))
+            .collect();
+        let span = unused.first().map(|p| &p.span).unwrap_or(span);
         return Err(GeneratingError::UnusedParameters(
-            names.keys().map(|s| s.to_owned()).collect(),
+            unused.iter().map(|p| p.name.clone()).collect(),
+            span.clone(),
         )
         .into());
     }
     Ok(())
 }
 
-fn filter_query(
-    parameters: &mut HashMap<String, String>,
-    query: &Vec<(String, ParameterValue)>,
+fn ensure_body_allowed(definition: &CallDefinition, parameter: &Parameter) -> Result<()> {
+    if matches!(definition.verb.as_ref(), "PATCH" | "POST" | "PUT") {
+        return Ok(());
+    }
+    Err(GeneratingError::GeneralError(
+        format!("{} does not support sending a body", definition.verb),
+        parameter.span.clone(),
+    )
+    .into())
+}
+
+fn filter_parts(
+    parameters: &mut HashMap<String, &Parameter>,
+    definition: &CallDefinition,
 ) -> Result<()> {
-    for (_, query) in query {
-        if let ParameterValue::Parameter(name) = query {
-            remove_string_param(parameters, name)?;
-        };
+    for part in &definition.parts {
+        let name = &part.parameter;
+        let parameter = remove_param(parameters, name, || {
+            definition.locate(&format!("{{{name}}}"))
+        })?;
+        ensure_body_allowed(definition, parameter)?;
+        if is_dictionary(&parameter.parameter_type) {
+            return Err(GeneratingError::GeneralError(
+                format!("{name} is a dictionary, which can't be sent as a part"),
+                parameter.span.clone(),
+            )
+            .into());
+        }
     }
     Ok(())
 }
 
-fn filter_path(parameters: &mut HashMap<String, String>, path: &Vec<String>) -> Result<()> {
-    for parameter in path {
-        remove_string_param(parameters, parameter)?;
+fn filter_query<'a>(
+    parameters: &mut HashMap<String, &'a Parameter>,
+    used: &mut HashMap<String, &'a Parameter>,
+    definition: &CallDefinition,
+) -> Result<()> {
+    for item in &definition.query {
+        if let ParameterValue::Object(name) = &item.value {
+            use_param(parameters, used, name, || {
+                definition.locate(&format!("{{{name}}}"))
+            })?;
+        }
+        if let ParameterValue::Map(name) = &item.value {
+            let parameter = use_param(parameters, used, name, || {
+                definition.locate(&format!("{{{name}*}}"))
+            })?;
+            ensure_usage(parameter, true)?;
+        }
+        if let ParameterValue::Parameter(name) = &item.value {
+            let parameter = use_param(parameters, used, name, || {
+                definition.locate(&format!(":{name}"))
+            })?;
+            ensure_usage(parameter, false)?;
+            if item.format.is_some() && !is_array(&parameter.parameter_type) {
+                return Err(GeneratingError::GeneralError(
+                    "Collection formats can only be used with arrays".into(),
+                    parameter.span.clone(),
+                )
+                .into());
+            }
+        };
     }
     Ok(())
 }
 
-fn filter_headers(
-    parameters: &mut HashMap<String, String>,
-    headers: &Vec<(String, ParameterValue)>,
+fn filter_path<'a>(
+    parameters: &mut HashMap<String, &'a Parameter>,
+    used: &mut HashMap<String, &'a Parameter>,
+    definition: &CallDefinition,
 ) -> Result<()> {
-    for (_, header) in headers {
-        if let ParameterValue::Parameter(name) = header {
-            remove_string_param(parameters, name)?;
-        };
+    for expression in definition.path.expressions() {
+        for variable in &expression.variables {
+            let name = variable.name.as_str();
+            let parameter = use_param(parameters, used, name, || {
+                definition.locate(&expression.text)
+            })?;
+            let type_ref = &parameter.parameter_type;
+            if expression.operator.requires_value() && matches!(type_ref, TypeRef::Optional(_)) {
+                return Err(GeneratingError::GeneralError(
+                    format!(
+                        "Optional parameters can't be used in {}, only in expressions with an operator",
+                        expression.text
+                    ),
+                    parameter.span.clone(),
+                )
+                .into());
+            }
+            if variable.prefix.is_some() && (is_array(type_ref) || is_dictionary(type_ref)) {
+                return Err(GeneratingError::GeneralError(
+                    "Prefixes can only be used with single values".into(),
+                    parameter.span.clone(),
+                )
+                .into());
+            }
+        }
     }
     Ok(())
 }
 
-fn remove_string_param(parameters: &mut HashMap<String, String>, parameter: &str) -> Result<()> {
-    if parameters.contains_key(parameter) {
-        let param = parameters.remove(parameter).unwrap();
-        match param.as_str() {
-            "String" => (),
-            other => {
-                return Err(GeneratingError::GeneralError(format!("Invalid type: {other}")).into())
+fn filter_headers<'a>(
+    parameters: &mut HashMap<String, &'a Parameter>,
+    used: &mut HashMap<String, &'a Parameter>,
+    definition: &CallDefinition,
+) -> Result<()> {
+    for (_, header) in &definition.headers {
+        match header {
+            HeaderValue::Parameter(name) => {
+                let parameter = use_param(parameters, used, name, || {
+                    definition.locate(&format!("{{{name}}}"))
+                })?;
+                ensure_single(parameter)?;
+                ensure_usage(parameter, false)?;
+            }
+            HeaderValue::Interpolated(segments) => {
+                for segment in segments {
+                    let name = match segment {
+                        Segment::Parameter(name) => name,
+                        Segment::Literal(_) => continue,
+                    };
+                    let parameter = use_param(parameters, used, name, || {
+                        definition.locate(&format!("{{{name}}}"))
+                    })?;
+                    ensure_single(parameter)?;
+                    ensure_usage(parameter, false)?;
+                }
+            }
+            HeaderValue::Map(name) => {
+                let parameter = use_param(parameters, used, name, || {
+                    definition.locate(&format!("{{{name}*}}"))
+                })?;
+                ensure_usage(parameter, true)?;
             }
+            _ => (),
         }
-        Ok(())
-    } else {
-        Err(GeneratingError::MissingParameter(parameter.to_owned()).into())
     }
+    Ok(())
+}
+
+/// Removes a parameter the first time it's used, later uses get the same parameter
+fn use_param<'a>(
+    parameters: &mut HashMap<String, &'a Parameter>,
+    used: &mut HashMap<String, &'a Parameter>,
+    parameter: &str,
+    usage: impl Fn() -> Span,
+) -> Result<&'a Parameter> {
+    if let Some(parameter) = used.get(parameter) {
+        return Ok(parameter);
+    }
+    let removed = remove_param(parameters, parameter, usage)?;
+    used.insert(parameter.to_owned(), removed);
+    Ok(removed)
+}
+
+/// Removes a parameter that is used as a string, `usage` locates where it was referenced
+fn remove_param<'a>(
+    parameters: &mut HashMap<String, &'a Parameter>,
+    parameter: &str,
+    usage: impl Fn() -> Span,
+) -> Result<&'a Parameter> {
+    match parameters.remove(parameter) {
+        Some(parameter) => Ok(parameter),
+        None => Err(GeneratingError::MissingParameter(parameter.to_owned(), usage()).into()),
+    }
+}
+
+/// Only query parameters can be left out or repeated, paths and headers need a single value
+fn ensure_single(parameter: &Parameter) -> Result<()> {
+    let message = match parameter.parameter_type {
+        TypeRef::Optional(_) => "Optional parameters can only be used in the query",
+        TypeRef::Array(_) => "Arrays can only be used in the query",
+        _ => return Ok(()),
+    };
+    Err(GeneratingError::GeneralError(message.into(), parameter.span.clone()).into())
+}
+
+/// Dictionaries can only be expanded with `{name*}`, which needs a dictionary
+fn ensure_usage(parameter: &Parameter, expanded: bool) -> Result<()> {
+    let is_dictionary = is_dictionary(&parameter.parameter_type);
+    match (expanded, is_dictionary) {
+        (true, false) => Err(GeneratingError::GeneralError(
+            format!("{} must be a dictionary to be expanded", parameter.name),
+            parameter.span.clone(),
+        )
+        .into()),
+        (false, true) => Err(GeneratingError::NotStringConvertible(
+            parameter.parameter_type.to_string(),
+            parameter.span.clone(),
+        )
+        .into()),
+        _ => Ok(()),
+    }
+}
+
+fn is_dictionary(type_ref: &TypeRef) -> bool {
+    match type_ref {
+        TypeRef::Optional(inner) => is_dictionary(inner),
+        TypeRef::Dictionary(..) => true,
+        _ => false,
+    }
+}
+
+fn is_array(type_ref: &TypeRef) -> bool {
+    match type_ref {
+        TypeRef::Optional(inner) => is_array(inner),
+        TypeRef::Array(_) => true,
+        _ => false,
+    }
+}
+
+/// Reads a `Body: {name}` line, returns false if the line declares something else
+pub(super) fn parse_body(definition: &mut CallDefinition, line: &str, span: &Span) -> Result<bool> {
+    let value = match line.strip_prefix("Body:") {
+        Some(value) => value.trim(),
+        None => return Ok(false),
+    };
+    definition.lines.push((line.to_owned(), span.clone()));
+    let name = value
+        .strip_prefix('{')
+        .and_then(|v| v.strip_suffix('}'))
+        .filter(|name| !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_'))
+        .ok_or_else(|| {
+            GeneratingError::GeneralError(
+                "Body must be declared as Body: {name}".into(),
+                definition.locate(line),
+            )
+        })?;
+    if definition.body.is_some() {
+        return Err(GeneratingError::GeneralError(
+            "Only one body can be declared".into(),
+            definition.locate(line),
+        )
+        .into());
+    }
+    definition.body = Some(name.to_owned());
+    Ok(true)
 }
 
-pub(super) fn parse_call_definition(call: &str) -> Result<CallDefinition> {
+pub(super) fn parse_call_definition(call: &str, span: &Span) -> Result<CallDefinition> {
+    let locate = |text: &str| find_span(call, span, text).unwrap_or_else(|| span.clone());
     let mut parts = call.split_whitespace();
     let verb = match parts.next() {
         Some(verb) => verb.to_owned(),
         None => {
-            return Err(GeneratingError::GeneralError("Call verb was not present".into()).into())
+            return Err(GeneratingError::GeneralError(
+                "Call verb was not present".into(),
+                span.clone(),
+            )
+            .into())
         }
     };
-    let allowed_verbs = vec![
-        "DELETE".to_owned(),
-        "GET".to_owned(),
-        "PATCH".to_owned(),
-        "POST".to_owned(),
-        "PUT".to_owned(),
-    ];
-    if !allowed_verbs.contains(&verb) {
-        return Err(GeneratingError::GeneralError("Invalid request verb".into()).into());
+    let allowed_verbs = ["DELETE", "GET", "PATCH", "POST", "PUT"];
+    if !allowed_verbs.contains(&verb.as_str()) {
+        return Err(
+            GeneratingError::GeneralError("Invalid request verb".into(), locate(&verb)).into(),
+        );
     }
 
     let path = match parts.next() {
         Some(path) => path.to_owned(),
         None => {
-            return Err(GeneratingError::GeneralError("Call path was not present".into()).into())
+            return Err(GeneratingError::GeneralError(
+                "Call path was not present".into(),
+                span.clone(),
+            )
+            .into())
         }
     };
     if let Some(value) = parts.next() {
-        return Err(GeneratingError::GeneralError(format!(
-            "Call format should be in format <VERB> /path?with=query, unknown token: {value}"
-        ))
+        return Err(GeneratingError::GeneralError(
+            format!(
+                "Call format should be in format <VERB> /path?with=query, unknown token: {value}"
+            ),
+            locate(value),
+        )
         .into());
     }
-    if !path.starts_with('/') {
-        return Err(GeneratingError::GeneralError("Path must start with /".into()).into());
+    if !path.starts_with('/') && !path.starts_with("{/") {
+        return Err(
+            GeneratingError::GeneralError("Path must start with /".into(), locate(&path)).into(),
+        );
+    }
+    let (path, query) = template::split_query(&path);
+    let path = UriTemplate::parse(path, locate)?;
+    if query.is_some() && path.expressions().any(|e| e.operator.is_query()) {
+        return Err(GeneratingError::GeneralError(
+            "Query expressions can't be combined with ?name=:value items".into(),
+            span.clone(),
+        )
+        .into());
     }
-    let mut path_parts = path.splitn(2, '?');
-    let path = path_parts
-        .next()
-        .map(|p| p.to_string())
-        .ok_or_else(|| GeneratingError::GeneralError("Unable to split path and query".into()))?;
-    let query_params = query::parse_params(path_parts.next())?;
-    let path_params = path::parse_params(&path)?;
+    let query_params = query::parse_params(query, span, locate)?;
     Ok(CallDefinition {
         verb,
         headers: vec![],
         path,
-        path_params,
         query: query_params,
+        body: None,
+        parts: vec![],
+        responses: Responses::default(),
+        span: span.clone(),
+        lines: vec![(call.to_owned(), span.clone())],
     })
 }
@@ -0,0 +1,136 @@
+use anyhow::Result;
+use swift_generator::{escape_identifier, string_literal, CodeBuilder, ControlType};
+use swift_parser::{Parameter, Span, TypeRef};
+
+use super::{errors::GeneratingError, models::Models, CallDefinition, HeaderValue};
+
+/// Part of a multipart body, `Part: avatar={image}`
+pub(super) struct Part {
+    /// Name of the part in the body
+    pub(super) name: String,
+    pub(super) parameter: String,
+}
+
+/// How the value of a part is written
+enum PartKind {
+    /// `MultipartFile`, written with its file name and content type
+    File,
+    /// Strings, numbers and enums with raw values, written as text
+    Text(String),
+    /// Any other `Encodable` value, written as JSON
+    Json,
+}
+
+/// Reads a `Part: name={parameter}` line, returns false if the line declares something else
+pub(super) fn parse_part(definition: &mut CallDefinition, line: &str, span: &Span) -> Result<bool> {
+    let value = match line.strip_prefix("Part:") {
+        Some(value) => value.trim(),
+        None => return Ok(false),
+    };
+    definition.lines.push((line.to_owned(), span.clone()));
+    let part = value.split_once('=').and_then(|(name, parameter)| {
+        let parameter = parameter.strip_prefix('{')?.strip_suffix('}')?;
+        let valid = !name.is_empty()
+            && !parameter.is_empty()
+            && parameter.chars().all(|c| c.is_alphanumeric() || c == '_');
+        valid.then(|| Part {
+            name: name.to_owned(),
+            parameter: parameter.to_owned(),
+        })
+    });
+    match part {
+        Some(part) => {
+            definition.parts.push(part);
+            Ok(true)
+        }
+        None => Err(GeneratingError::GeneralError(
+            "Parts must be declared as Part: name={parameter}".into(),
+            definition.locate(line),
+        )
+        .into()),
+    }
+}
+
+/// Parts are sent as `multipart/form-data`, which is declared for the call unless it already is
+pub(super) fn ensure_content_type(definition: &mut CallDefinition) -> Result<()> {
+    let content_type = definition
+        .headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-type"));
+    match content_type {
+        None => definition.headers.push((
+            "Content-Type".into(),
+            HeaderValue::Value("multipart/form-data".into()),
+        )),
+        Some((_, HeaderValue::Value(value))) if value == "multipart/form-data" => (),
+        Some((name, _)) => {
+            return Err(GeneratingError::GeneralError(
+                "Parts can only be sent as multipart/form-data".into(),
+                definition.locate(name),
+            )
+            .into())
+        }
+    }
+    Ok(())
+}
+
+/// Adds the parts to the body, `boundary` is declared by the content type header
+pub(super) fn add_parts(
+    code: &mut CodeBuilder,
+    parts: &[Part],
+    parameters: &[Parameter],
+    models: &Models,
+    encoder: &str,
+) -> Result<()> {
+    let mut statements = vec![];
+    let mut json = false;
+    for part in parts {
+        let parameter = match parameters.iter().find(|p| p.name == part.parameter) {
+            Some(parameter) => parameter,
+            None => continue,
+        };
+        let name = escape_identifier(&parameter.name);
+        let (optional, type_ref) = match &parameter.parameter_type {
+            TypeRef::Optional(inner) => (true, inner.as_ref()),
+            type_ref => (false, type_ref),
+        };
+        let kind = if type_ref.name() == Some("MultipartFile") {
+            PartKind::File
+        } else if models.is_plain_value(type_ref) {
+            PartKind::Text(models.string_value(&name, type_ref, &parameter.span)?)
+        } else {
+            models.ensure_encodable(type_ref, &parameter.span)?;
+            PartKind::Json
+        };
+        let part_name = string_literal(&part.name);
+        let statement = match kind {
+            PartKind::File => format!("parts.add(name: {part_name}, file: {name})"),
+            PartKind::Text(value) => format!("parts.add(name: {part_name}, value: {value})"),
+            PartKind::Json => {
+                json = true;
+                format!("parts.add(name: {part_name}, json: try encoder.encode({name}))")
+            }
+        };
+        statements.push((optional.then_some(name), statement));
+    }
+
+    if json {
+        code.add_statement(&format!("let encoder = {encoder}"));
+    }
+    code.add_statement("var parts = MultipartBody(boundary: boundary)");
+    for (optional, statement) in statements {
+        match optional {
+            // Left out of the body when nil
+            Some(name) => {
+                let mut add = CodeBuilder::default();
+                add.add_statement(&statement);
+                code.add_control(ControlType::If, &format!("let {name} = {name}"), add);
+            }
+            None => {
+                code.add_statement(&statement);
+            }
+        }
+    }
+    code.add_statement("request.httpBody = parts.build()");
+    Ok(())
+}
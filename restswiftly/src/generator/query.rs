@@ -1,45 +1,196 @@
 use anyhow::Result;
-use swift_generator::CodeBuilder;
+use swift_generator::{escape_identifier, string_literal, CodeBuilder, ControlType};
+use swift_parser::Span;
 
-use super::{errors::GeneratingError, ParameterValue};
+use super::{add_entries, errors::GeneratingError, ParameterValue, Values};
 
-pub(super) fn add_parameters(code: &mut CodeBuilder, query: Vec<(String, ParameterValue)>) {
+/// Item of the query, `q=:query`
+pub(super) struct QueryItem {
+    pub(super) name: String,
+    pub(super) value: ParameterValue,
+    /// How array parameters are written, declared as `:tags(comma)`
+    pub(super) format: Option<CollectionFormat>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(super) enum CollectionFormat {
+    /// `tag=a&tag=b`
+    #[default]
+    Multi,
+    /// `tag[]=a&tag[]=b`
+    Brackets,
+    /// `tags=a,b`
+    Comma,
+    /// `tags=a|b`
+    Pipe,
+    /// `tags=a%20b`
+    Space,
+}
+
+impl CollectionFormat {
+    fn parse(format: &str) -> Option<CollectionFormat> {
+        match format {
+            "multi" => Some(CollectionFormat::Multi),
+            "brackets" => Some(CollectionFormat::Brackets),
+            "comma" => Some(CollectionFormat::Comma),
+            "pipe" => Some(CollectionFormat::Pipe),
+            "space" => Some(CollectionFormat::Space),
+            _ => None,
+        }
+    }
+
+    fn separator(&self) -> Option<&'static str> {
+        match self {
+            CollectionFormat::Multi | CollectionFormat::Brackets => None,
+            CollectionFormat::Comma => Some(","),
+            CollectionFormat::Pipe => Some("|"),
+            CollectionFormat::Space => Some(" "),
+        }
+    }
+}
+
+pub(super) fn add_parameters(code: &mut CodeBuilder, query: &[QueryItem], values: &Values) {
     code.add_statement("var urlComponents = URLComponents(string: url.absoluteString)!")
         .add_statement("var queryItems = urlComponents.queryItems ?? []");
-    query.into_iter().for_each(|(name, value)| {
-        let statement = match value {
+    for item in query {
+        let name = string_literal(&item.name);
+        let parameter = match &item.value {
             ParameterValue::None => {
-                format!(r#"queryItems.append(URLQueryItem(name: "{name}", value: nil))"#,)
-            }
-            ParameterValue::Parameter(parameter) => {
-                format!(r#"queryItems.append(URLQueryItem(name: "{name}", value: {parameter}))"#,)
+                code.add_statement(&format!(
+                    "queryItems.append(URLQueryItem(name: {name}, value: nil))"
+                ));
+                continue;
             }
             ParameterValue::Value(value) => {
-                format!(r#"queryItems.append(URLQueryItem(name: "{name}", value: "{value}"))"#,)
+                code.add_statement(&format!(
+                    "queryItems.append(URLQueryItem(name: {name}, value: {}))",
+                    string_literal(value)
+                ));
+                continue;
             }
+            ParameterValue::Parameter(parameter)
+            | ParameterValue::Map(parameter)
+            | ParameterValue::Object(parameter) => parameter,
         };
-        code.add_statement(&statement);
-    });
+
+        if values.is_optional(parameter) {
+            // Left out of the query entirely when nil
+            let mut append = CodeBuilder::default();
+            add_parameter(&mut append, item, parameter, values);
+            let parameter = escape_identifier(parameter);
+            code.add_control(
+                ControlType::If,
+                &format!("let {parameter} = {parameter}"),
+                append,
+            );
+        } else {
+            add_parameter(code, item, parameter, values);
+        }
+    }
     code.add_statement("urlComponents.queryItems = queryItems")
         .add_statement("url = urlComponents.url!");
 }
 
-pub(super) fn parse_params(query: Option<&str>) -> Result<Vec<(String, ParameterValue)>> {
+fn add_parameter(code: &mut CodeBuilder, item: &QueryItem, parameter: &str, values: &Values) {
+    match item.value {
+        ParameterValue::Map(_) => {
+            let statement = "queryItems.append(URLQueryItem(name: {key}, value: {value}))";
+            add_entries(code, parameter, values, statement);
+            return;
+        }
+        ParameterValue::Object(_) => {
+            code.add_statement(&format!(
+                "queryItems.append(contentsOf: try QueryEncoder().encode({}))",
+                escape_identifier(parameter)
+            ));
+            return;
+        }
+        _ => (),
+    }
+    match values.element(parameter) {
+        Some(element) => add_collection(code, item, parameter, element),
+        None => {
+            code.add_statement(&format!(
+                "queryItems.append(URLQueryItem(name: {}, value: {}))",
+                string_literal(&item.name),
+                values.get(parameter)
+            ));
+        }
+    }
+}
+
+/// Adds the items of an array parameter, `element` converts each element to a string
+fn add_collection(code: &mut CodeBuilder, item: &QueryItem, parameter: &str, element: &str) {
+    let format = item.format.unwrap_or_default();
+    let parameter = escape_identifier(parameter);
+    let name = match format {
+        CollectionFormat::Brackets => string_literal(&format!("{}[]", item.name)),
+        _ => string_literal(&item.name),
+    };
+
+    match format.separator() {
+        Some(separator) => {
+            let separator = string_literal(separator);
+            let joined = if element == "element" {
+                format!("{parameter}.joined(separator: {separator})")
+            } else {
+                format!("{parameter}.map {{ element in {element} }}.joined(separator: {separator})")
+            };
+            code.add_statement(&format!(
+                "queryItems.append(URLQueryItem(name: {name}, value: {joined}))"
+            ));
+        }
+        None => {
+            let mut append = CodeBuilder::default();
+            append.add_statement(&format!(
+                "queryItems.append(URLQueryItem(name: {name}, value: {element}))"
+            ));
+            code.add_control(ControlType::For, &format!("element in {parameter}"), append);
+        }
+    }
+}
+
+pub(super) fn parse_params(
+    query: Option<&str>,
+    span: &Span,
+    locate: impl Fn(&str) -> Span,
+) -> Result<Vec<QueryItem>> {
     let query = match query {
         Some(value) => value,
         None => return Ok(vec![]),
     };
     let mut query_values = vec![];
     for query_item in query.split('&') {
+        if let Some(expanded) = query_item
+            .strip_prefix('{')
+            .and_then(|i| i.strip_suffix('}'))
+        {
+            let value = match expanded.strip_suffix('*') {
+                Some(map) => ParameterValue::Map(map.into()),
+                None => ParameterValue::Object(expanded.into()),
+            };
+            query_values.push(QueryItem {
+                name: String::new(),
+                value,
+                format: None,
+            });
+            continue;
+        }
+
         let mut parts = query_item.split('=');
         let name = parts
             .next()
-            .ok_or_else(|| GeneratingError::GeneralError("Query name required".into()))?
+            .ok_or_else(|| {
+                GeneratingError::GeneralError("Query name required".into(), span.clone())
+            })?
             .to_string();
+        let mut format = None;
         let value = match parts.next() {
             Some(value) => {
-                if let Some(suffix) = value.strip_prefix(':') {
-                    ParameterValue::Parameter(suffix.into())
+                if let Some(parameter) = value.strip_prefix(':') {
+                    let (parameter, collection_format) = split_format(parameter, &locate)?;
+                    format = collection_format;
+                    ParameterValue::Parameter(parameter.into())
                 } else {
                     ParameterValue::Value(value.into())
                 }
@@ -47,8 +198,33 @@ pub(super) fn parse_params(query: Option<&str>) -> Result<Vec<(String, Parameter
             None => ParameterValue::None,
         };
 
-        query_values.push((name, value))
+        query_values.push(QueryItem {
+            name,
+            value,
+            format,
+        })
     }
 
     Ok(query_values)
 }
+
+/// Splits `tags(comma)` into the parameter and its collection format
+fn split_format(
+    parameter: &str,
+    locate: impl Fn(&str) -> Span,
+) -> Result<(&str, Option<CollectionFormat>)> {
+    let (parameter, format) = match parameter.strip_suffix(')').and_then(|p| p.split_once('(')) {
+        Some(split) => split,
+        None => return Ok((parameter, None)),
+    };
+    match CollectionFormat::parse(format) {
+        Some(format) => Ok((parameter, Some(format))),
+        None => Err(GeneratingError::GeneralError(
+            format!(
+                "Unknown collection format {format}, expected multi, brackets, comma, pipe or space"
+            ),
+            locate(&format!("({format})")),
+        )
+        .into()),
+    }
+}
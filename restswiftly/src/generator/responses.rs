@@ -0,0 +1,121 @@
+use anyhow::Result;
+use swift_generator::{CodeBuilder, ControlType};
+use swift_parser::{read_type_ref, Span, TypeRef};
+
+use super::{errors::GeneratingError, find_span};
+
+/// Status codes a call accepts as successful, either single codes or inclusive ranges
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct SuccessCodes(Vec<(u16, u16)>);
+
+impl Default for SuccessCodes {
+    fn default() -> Self {
+        SuccessCodes(vec![(200, 200)])
+    }
+}
+
+impl SuccessCodes {
+    /// Parses the value of a `Success: 200-299` or `Success: 201, 204` line
+    pub(super) fn parse(line: &str, value: &str, span: &Span) -> Result<SuccessCodes> {
+        let locate = |text: &str| find_span(line, span, text).unwrap_or_else(|| span.clone());
+
+        let mut codes = vec![];
+        for item in value.split(',').map(str::trim) {
+            let (start, end) = match item.split_once('-') {
+                Some((start, end)) => (parse_code(start.trim()), parse_code(end.trim())),
+                None => (parse_code(item), parse_code(item)),
+            };
+            match (start, end) {
+                (Some(start), Some(end)) if start <= end => codes.push((start, end)),
+                _ => {
+                    return Err(GeneratingError::GeneralError(
+                        format!("Invalid status code: {item}"),
+                        locate(item),
+                    )
+                    .into())
+                }
+            }
+        }
+        Ok(SuccessCodes(codes))
+    }
+
+    /// Swift condition checking `statusCode` is one of the codes
+    pub(super) fn condition(&self) -> String {
+        if self.0.len() > 1 && self.0.iter().all(|(start, end)| start == end) {
+            let codes: Vec<_> = self.0.iter().map(|(code, _)| code.to_string()).collect();
+            return format!("[{}].contains(statusCode)", codes.join(", "));
+        }
+
+        self.0
+            .iter()
+            .map(|(start, end)| {
+                if start == end {
+                    format!("statusCode == {start}")
+                } else {
+                    format!("({start}...{end}).contains(statusCode)")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" || ")
+    }
+}
+
+fn parse_code(code: &str) -> Option<u16> {
+    code.parse().ok().filter(|code| (100..=599).contains(code))
+}
+
+/// Response handling declared with `Success` and `Error` lines, for a call or the whole service
+#[derive(Clone, Default)]
+pub(super) struct Responses {
+    pub(super) success: Option<SuccessCodes>,
+    /// Type unsuccessful responses are decoded into, with the location it was declared at
+    pub(super) error: Option<(TypeRef, Span)>,
+}
+
+impl Responses {
+    /// Reads `line` if it is a `Success` or `Error` line, returns whether it was one
+    pub(super) fn parse(&mut self, line: &str, span: &Span) -> Result<bool> {
+        let locate = |text: &str| find_span(line, span, text).unwrap_or_else(|| span.clone());
+
+        if let Some(codes) = line.strip_prefix("Success:") {
+            self.success = Some(SuccessCodes::parse(line, codes, span)?);
+        } else if let Some(error) = line.strip_prefix("Error:") {
+            let error = error.trim();
+            let error_span = locate(error);
+            self.error = Some((read_type_ref(error, &error_span)?, error_span));
+        } else {
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    /// Values of `self`, with the ones it doesn't declare taken from `service`
+    pub(super) fn or(&self, service: &Responses) -> Responses {
+        Responses {
+            success: self.success.clone().or_else(|| service.success.clone()),
+            error: self.error.clone().or_else(|| service.error.clone()),
+        }
+    }
+
+    /// Adds the check of the status code, throwing the declared error or `HTTPError`
+    pub(super) fn add_check(&self, code: &mut CodeBuilder, decoder: &str) {
+        let mut failure = CodeBuilder::default();
+        if let Some((error, _)) = &self.error {
+            let mut throw = CodeBuilder::default();
+            throw.add_statement("throw TypedHTTPError(response: response, body: error)");
+            failure.add_control(
+                ControlType::If,
+                &format!("let error = try? {decoder}.decode({error}.self, from: data)"),
+                throw,
+            );
+        }
+        failure.add_statement("throw HTTPError(response: response, body: data)");
+
+        code.add_statement("let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0")
+            .add_control(
+                ControlType::Guard,
+                &self.success.clone().unwrap_or_default().condition(),
+                failure,
+            );
+    }
+}
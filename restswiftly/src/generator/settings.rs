@@ -0,0 +1,31 @@
+use swift_generator::AccessModifier;
+
+/// Generation settings shared by all services
+#[derive(Clone)]
+pub struct Settings {
+    /// Appended to the protocol name to name the generated class
+    pub suffix: String,
+    /// Access level of the generated class and its members, implicit when not set
+    pub access: Option<AccessModifier>,
+    /// Expression creating the encoder for JSON bodies
+    pub encoder: String,
+    /// Expression creating the decoder for responses
+    pub decoder: String,
+    /// Expression of the `URLSession` requests are sent with
+    pub session: String,
+    /// Headers added to every request, unless the call sets the same header
+    pub headers: Vec<(String, String)>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            suffix: "Impl".into(),
+            access: None,
+            encoder: "JSONEncoder()".into(),
+            decoder: "JSONDecoder()".into(),
+            session: "URLSession.shared".into(),
+            headers: vec![],
+        }
+    }
+}
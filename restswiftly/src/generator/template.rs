@@ -0,0 +1,287 @@
+use anyhow::Result;
+use std::{collections::HashSet, fmt::Write};
+use swift_generator::{escape_identifier, string_literal};
+use swift_parser::Span;
+
+use super::{errors::GeneratingError, Values};
+
+/// Path of a call, an RFC 6570 URI template such as `/users/{id}/files{/path*}{?fields}`
+pub(super) struct UriTemplate {
+    parts: Vec<TemplatePart>,
+}
+
+enum TemplatePart {
+    Literal(String),
+    Expression(Expression),
+}
+
+/// Expression of the template, `{?q,limit}`
+pub(super) struct Expression {
+    /// Source of the expression, including the braces
+    pub(super) text: String,
+    pub(super) operator: Operator,
+    pub(super) variables: Vec<Variable>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum Operator {
+    /// `{var}`
+    Simple,
+    /// `{+var}`, reserved characters such as slashes are not encoded
+    Reserved,
+    /// `{#var}`
+    Fragment,
+    /// `{.var}`
+    Label,
+    /// `{/var}`
+    PathSegment,
+    /// `{;var}`
+    PathParameter,
+    /// `{?var}`
+    Query,
+    /// `{&var}`
+    QueryContinuation,
+}
+
+pub(super) struct Variable {
+    pub(super) name: String,
+    /// Lists and dictionaries are expanded into an item for every value, `{tags*}`
+    pub(super) explode: bool,
+    /// Only the first characters of the value are used, `{id:3}`
+    pub(super) prefix: Option<u16>,
+}
+
+impl UriTemplate {
+    pub(super) fn parse(path: &str, locate: impl Fn(&str) -> Span) -> Result<UriTemplate> {
+        let mut parts = vec![];
+        let mut rest = path;
+        while let Some(start) = rest.find(['{', '}']) {
+            if start > 0 {
+                parts.push(TemplatePart::Literal(rest[..start].to_owned()));
+            }
+            rest = &rest[start..];
+            if rest.starts_with('}') {
+                return Err(GeneratingError::GeneralError(
+                    "Unmatched } in path".into(),
+                    locate("}"),
+                )
+                .into());
+            }
+            let end = rest.find('}').ok_or_else(|| {
+                GeneratingError::GeneralError("Unclosed expression in path".into(), locate(rest))
+            })?;
+            let expression = Expression::parse(&rest[..=end], &locate)?;
+            parts.push(TemplatePart::Expression(expression));
+            rest = &rest[end + 1..];
+        }
+        if !rest.is_empty() {
+            parts.push(TemplatePart::Literal(rest.to_owned()));
+        }
+        Ok(UriTemplate { parts })
+    }
+
+    pub(super) fn expressions(&self) -> impl Iterator<Item = &Expression> {
+        self.parts.iter().filter_map(|part| match part {
+            TemplatePart::Expression(expression) => Some(expression),
+            TemplatePart::Literal(_) => None,
+        })
+    }
+
+    /// Swift expression of the path with all expressions replaced by their values
+    pub(super) fn expand(&self, values: &Values) -> String {
+        let mut path = string_literal(&self.source());
+        let mut expanded = HashSet::new();
+        for expression in self.expressions() {
+            if !expanded.insert(&expression.text) {
+                continue;
+            }
+            write!(
+                path,
+                ".replacingOccurrences(of: {}, with: {})",
+                string_literal(&expression.text),
+                expression.expand(values)
+            )
+            .expect("Unable to concat string");
+        }
+        path
+    }
+
+    fn source(&self) -> String {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                TemplatePart::Literal(literal) => literal.as_str(),
+                TemplatePart::Expression(expression) => expression.text.as_str(),
+            })
+            .collect()
+    }
+}
+
+impl Expression {
+    fn parse(text: &str, locate: impl Fn(&str) -> Span) -> Result<Expression> {
+        let error = |message: String| GeneratingError::GeneralError(message, locate(text));
+        let body = &text[1..text.len() - 1];
+        let (operator, list) = match body.chars().next().and_then(Operator::parse) {
+            Some(operator) => (operator, &body[1..]),
+            None => (Operator::Simple, body),
+        };
+        if let Some(reserved @ ('=' | ',' | '!' | '@' | '|')) = body.chars().next() {
+            return Err(error(format!(
+                "Operator {reserved} is reserved for future extensions"
+            ))
+            .into());
+        }
+
+        let mut variables = vec![];
+        for spec in list.split(',') {
+            let (name, explode, prefix) = if let Some(name) = spec.strip_suffix('*') {
+                (name, true, None)
+            } else if let Some((name, length)) = spec.split_once(':') {
+                match length.parse() {
+                    Ok(length @ 1..=9999) => (name, false, Some(length)),
+                    _ => {
+                        return Err(error(format!(
+                            "Prefix length must be between 1 and 9999, found {length}"
+                        ))
+                        .into())
+                    }
+                }
+            } else {
+                (spec, false, None)
+            };
+            if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                return Err(error(format!("Invalid variable name in {text}: {name}")).into());
+            }
+            variables.push(Variable {
+                name: name.to_owned(),
+                explode,
+                prefix,
+            });
+        }
+
+        Ok(Expression {
+            text: text.to_owned(),
+            operator,
+            variables,
+        })
+    }
+
+    /// Swift expression of the expanded value
+    fn expand(&self, values: &Values) -> String {
+        if let [variable] = self.variables.as_slice() {
+            if let Some(allowed) = self.allowed_characters(variable, values) {
+                return format!(
+                    "{}.addingPercentEncoding(withAllowedCharacters: .{allowed})!",
+                    values.get(&variable.name)
+                );
+            }
+        }
+        let variables: Vec<_> = self.variables.iter().map(|v| v.expand(values)).collect();
+        format!(
+            "URITemplate.expand({}, [{}])",
+            string_literal(self.operator.symbol()),
+            variables.join(", ")
+        )
+    }
+
+    /// Single values of simple and reserved expressions are encoded in place, without `URITemplate`
+    fn allowed_characters(&self, variable: &Variable, values: &Values) -> Option<&'static str> {
+        let name = variable.name.as_str();
+        if variable.prefix.is_some()
+            || values.is_optional(name)
+            || values.element(name).is_some()
+            || values.entry_value(name).is_some()
+        {
+            return None;
+        }
+        match self.operator {
+            // Unlike the RFC, `{path*}` keeps its slashes, for values spanning several segments
+            Operator::Simple if variable.explode => Some("uriTemplatePath"),
+            Operator::Simple => Some("uriTemplateUnreserved"),
+            Operator::Reserved => Some("uriTemplateReserved"),
+            _ => None,
+        }
+    }
+}
+
+impl Operator {
+    fn parse(symbol: char) -> Option<Operator> {
+        match symbol {
+            '+' => Some(Operator::Reserved),
+            '#' => Some(Operator::Fragment),
+            '.' => Some(Operator::Label),
+            '/' => Some(Operator::PathSegment),
+            ';' => Some(Operator::PathParameter),
+            '?' => Some(Operator::Query),
+            '&' => Some(Operator::QueryContinuation),
+            _ => None,
+        }
+    }
+
+    fn symbol(&self) -> &'static str {
+        match self {
+            Operator::Simple => "",
+            Operator::Reserved => "+",
+            Operator::Fragment => "#",
+            Operator::Label => ".",
+            Operator::PathSegment => "/",
+            Operator::PathParameter => ";",
+            Operator::Query => "?",
+            Operator::QueryContinuation => "&",
+        }
+    }
+
+    /// Simple and reserved expressions always write their value, the others leave out undefined ones
+    pub(super) fn requires_value(&self) -> bool {
+        matches!(self, Operator::Simple | Operator::Reserved)
+    }
+
+    pub(super) fn is_query(&self) -> bool {
+        matches!(self, Operator::Query | Operator::QueryContinuation)
+    }
+}
+
+impl Variable {
+    /// Swift expression of the `URITemplate.Variable` holding the value
+    fn expand(&self, values: &Values) -> String {
+        let name = escape_identifier(&self.name);
+        let mut value = if let Some(element) = values.element(&self.name) {
+            if element == "element" {
+                format!(".list({name})")
+            } else {
+                format!(".list({name}.map {{ element in {element} }})")
+            }
+        } else if let Some(value) = values.entry_value(&self.name) {
+            format!(".pairs({name}.sorted(by: {{ $0.key < $1.key }}).map {{ key, value in (key, {value}) }})")
+        } else {
+            format!(".string({})", values.get(&self.name))
+        };
+        if values.is_optional(&self.name) {
+            value = format!("{name}.map {{ {name} in {value} }}");
+        }
+
+        let mut variable = format!(".init({}, {value}", string_literal(&self.name));
+        if self.explode {
+            variable.push_str(", explode: true");
+        }
+        if let Some(prefix) = self.prefix {
+            write!(variable, ", prefix: {prefix}").expect("Unable to concat string");
+        }
+        variable.push(')');
+        variable
+    }
+}
+
+/// Splits the path at the first `?` outside of expressions, the rest is the query
+pub(super) fn split_query(path: &str) -> (&str, Option<&str>) {
+    let mut depth = 0;
+    for (index, c) in path.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            '?' if depth == 0 => return (&path[..index], Some(&path[index + 1..])),
+            _ => (),
+        }
+    }
+    (path, None)
+}
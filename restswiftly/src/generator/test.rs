@@ -4,10 +4,10 @@ use std::{
 };
 
 use anyhow::Result;
-use swift_generator::Options;
+use swift_generator::{AccessModifier, Options};
 use swift_parser::Definition;
 
-use crate::generator::Generator;
+use crate::generator::{Generator, Settings};
 
 use super::errors::GeneratingError;
 
@@ -24,6 +24,24 @@ fn generated_files_correct() -> Result<()> {
         "Headers",
         "FormEncoding",
         "MultipartEncoding",
+        "Documented",
+        "Types",
+        "Models",
+        "Identifiers",
+        "StatusCodes",
+        "Errors",
+        "ParameterTypes",
+        "OptionalQuery",
+        "ArrayQuery",
+        "Maps",
+        "QueryObject",
+        "PathEncoding",
+        "UriTemplates",
+        "InterpolatedHeaders",
+        "EscapedLiterals",
+        "AnnotatedBody",
+        "RawBodies",
+        "MultipartParts",
     ]
     .into_iter()
     {
@@ -35,11 +53,13 @@ fn generated_files_correct() -> Result<()> {
 
 #[test]
 fn file_fails_generation() -> Result<()> {
-    let input_file = File::open(format!("../samples/Failing.swift"))?;
+    let input_file = File::open("../samples/Failing.swift")?;
     let definitions = swift_parser::read_definitions(input_file)?;
 
     let (name, definitions) = match definitions.into_iter().next() {
-        Some(Definition::Protocol(name, definitions)) => (name, definitions),
+        Some(Definition::Protocol {
+            name, definitions, ..
+        }) => (name, definitions),
         _ => panic!("Invalid test definition"),
     };
 
@@ -48,8 +68,9 @@ fn file_fails_generation() -> Result<()> {
         Ok(_) => panic!("Expected failure"),
         Err(error) => {
             let error: GeneratingError = error.downcast()?;
-            if let GeneratingError::UnusedParameters(parameters) = error {
+            if let GeneratingError::UnusedParameters(parameters, span) = error {
                 assert_eq!(vec!["query".to_string()], parameters);
+                assert_eq!((3, 11, 5), (span.line, span.column, span.length));
             } else {
                 panic!("Unexpected error variant: {error}");
             }
@@ -59,25 +80,410 @@ fn file_fails_generation() -> Result<()> {
     Ok(())
 }
 
-fn generated_file_correct(name: &str) -> Result<()> {
-    let input_file = File::open(format!("../samples/{name}.swift"))?;
+#[test]
+fn form_body_fields_must_be_writable() -> Result<()> {
+    let input_file = File::open("../samples/FailingForm.swift")?;
     let definitions = swift_parser::read_definitions(input_file)?;
-    assert_eq!(1, definitions.len()); // sanity check, only expect one file per test
 
-    let (name, definitions) = match definitions.into_iter().next() {
-        Some(Definition::Protocol(name, definitions)) => (name, definitions),
+    let services = match definitions.last() {
+        Some(Definition::Protocol { definitions, .. }) => definitions,
         _ => panic!("Invalid test definition"),
     };
 
-    let class = Generator::new().generate_service(&name, &definitions)?;
+    let result = Generator::new()
+        .add_models(&definitions)
+        .generate_service("FailingForm", services);
+    match result {
+        Ok(_) => panic!("Expected failure"),
+        Err(error) => {
+            let error: GeneratingError = error.downcast()?;
+            if let GeneratingError::NotFormEncodable(type_name, reason, span) = error {
+                assert_eq!("Tags", type_name);
+                assert_eq!(
+                    "property visibility of type Visibility can't be written as a form field",
+                    reason
+                );
+                assert_eq!((14, 12, 4), (span.line, span.column, span.length));
+            } else {
+                panic!("Unexpected error variant: {error}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn form_body_must_be_struct() -> Result<()> {
+    let source = "enum Visibility: String, Encodable {
+\tcase everyone
+}
+protocol Service {
+\t// POST /visibility
+\t// Content-Type: application/x-www-form-urlencoded
+\tfunc post(body: Visibility) async throws
+}
+";
+    let error = generation_error(source)?;
+    assert_eq!(
+        "Visibility cannot be form encoded: only structs and classes can be form encoded",
+        error.to_string()
+    );
+    assert_eq!(
+        (7, 12, 4),
+        (error.span().line, error.span().column, error.span().length)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn body_must_be_encodable() -> Result<()> {
+    let source = "struct Hello: Decodable {}
+protocol Service {
+\t// POST /hello
+\tfunc post(body: Hello) async throws -> Hello
+}
+";
+    let error = generation_error(source)?;
+    assert_eq!("Hello does not conform to Encodable", error.to_string());
+    assert_eq!(
+        (4, 12, 4),
+        (error.span().line, error.span().column, error.span().length)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn success_codes_must_be_valid() -> Result<()> {
+    let source = "protocol Service {
+\t// GET /get
+\t// Success: 200-99
+\tfunc get() async throws
+}
+";
+    let error = generation_error(source)?;
+    assert_eq!(
+        "General error: Invalid status code: 200-99",
+        error.to_string()
+    );
+    assert_eq!(
+        (3, 14, 6),
+        (error.span().line, error.span().column, error.span().length)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn error_type_must_be_decodable() -> Result<()> {
+    let source = "struct ApiError: Encodable {}
+protocol Service {
+\t// GET /get
+\t// Error: ApiError
+\tfunc get() async throws
+}
+";
+    let error = generation_error(source)?;
+    assert_eq!("ApiError does not conform to Decodable", error.to_string());
+    assert_eq!(
+        (4, 12, 8),
+        (error.span().line, error.span().column, error.span().length)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn parameters_must_be_string_convertible() -> Result<()> {
+    let source = "struct Filter: Codable {}
+protocol Service {
+\t// GET /get?filter=:filter
+\tfunc get(filter: Filter) async throws
+}
+";
+    let error = generation_error(source)?;
+    assert_eq!("Filter cannot be converted to a string", error.to_string());
+    assert_eq!(
+        (4, 11, 6),
+        (error.span().line, error.span().column, error.span().length)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn path_parameters_must_not_be_optional() -> Result<()> {
+    let source = "protocol Service {
+\t// GET /users/{id}
+\tfunc get(id: Int?) async throws
+}
+";
+    let error = generation_error(source)?;
+    assert_eq!(
+        "General error: Optional parameters can't be used in {id}, only in expressions with an operator",
+        error.to_string()
+    );
+    assert_eq!(
+        (3, 11, 2),
+        (error.span().line, error.span().column, error.span().length)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn template_operators_must_be_known() -> Result<()> {
+    let source = "protocol Service {
+\t// GET /users/{=id}
+\tfunc get(id: Int) async throws
+}
+";
+    let error = generation_error(source)?;
+    assert_eq!(
+        "General error: Operator = is reserved for future extensions",
+        error.to_string()
+    );
+    assert_eq!(
+        (2, 16, 5),
+        (error.span().line, error.span().column, error.span().length)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn header_placeholders_must_be_closed() -> Result<()> {
+    let source = "protocol Service {
+\t// GET /profile
+\t// Authorization: Bearer {token
+\tfunc get(token: String) async throws
+}
+";
+    let error = generation_error(source)?;
+    assert_eq!(
+        "General error: Unclosed placeholder in header",
+        error.to_string()
+    );
+    assert_eq!(
+        (3, 27, 6),
+        (error.span().line, error.span().column, error.span().length)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn only_one_body_declared() -> Result<()> {
+    let source = "protocol Service {
+\t// POST /users
+\t// Body: {user}
+\t// Body: {other}
+\tfunc create(user: String, other: String) async throws
+}
+";
+    let error = generation_error(source)?;
+    assert_eq!(
+        "General error: Only one body can be declared",
+        error.to_string()
+    );
+    assert_eq!(
+        (4, 5, 13),
+        (error.span().line, error.span().column, error.span().length)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn raw_bodies_must_not_be_multipart() -> Result<()> {
+    let source = "protocol Service {
+\t// POST /upload
+\t// Content-Type: multipart/form-data
+\tfunc upload(body: Data) async throws
+}
+";
+    let error = generation_error(source)?;
+    assert_eq!(
+        "General error: Raw bodies can't be sent as multipart/form-data",
+        error.to_string()
+    );
+    assert_eq!(
+        (3, 5, 12),
+        (error.span().line, error.span().column, error.span().length)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn file_bodies_must_not_be_optional() -> Result<()> {
+    let source = "protocol Service {
+\t// POST /upload
+\tfunc upload(body: URL?) async throws
+}
+";
+    let error = generation_error(source)?;
+    assert_eq!(
+        "General error: File bodies can't be optional",
+        error.to_string()
+    );
+    assert_eq!(
+        (3, 14, 4),
+        (error.span().line, error.span().column, error.span().length)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn parts_must_not_have_body() -> Result<()> {
+    let source = "protocol Service {
+\t// POST /upload
+\t// Body: {body}
+\t// Part: file={file}
+\tfunc upload(body: String, file: MultipartFile) async throws
+}
+";
+    let error = generation_error(source)?;
+    assert_eq!(
+        "General error: Parts can't be sent with a body",
+        error.to_string()
+    );
+    assert_eq!(
+        (5, 14, 4),
+        (error.span().line, error.span().column, error.span().length)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn collection_format_must_be_known() -> Result<()> {
+    let source = "protocol Service {
+\t// GET /search?tag=:tags(tabs)
+\tfunc search(tags: [String]) async throws
+}
+";
+    let error = generation_error(source)?;
+    assert_eq!(
+        "General error: Unknown collection format tabs, expected multi, brackets, comma, pipe or space",
+        error.to_string()
+    );
+    assert_eq!(
+        (2, 26, 6),
+        (error.span().line, error.span().column, error.span().length)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn expanded_parameters_must_be_dictionaries() -> Result<()> {
+    let source = "protocol Service {
+\t// GET /search?{filters*}
+\tfunc search(filters: [String]) async throws
+}
+";
+    let error = generation_error(source)?;
+    assert_eq!(
+        "General error: filters must be a dictionary to be expanded",
+        error.to_string()
+    );
+    assert_eq!(
+        (3, 14, 7),
+        (error.span().line, error.span().column, error.span().length)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn query_objects_must_be_encodable() -> Result<()> {
+    let source = "struct Filter: Decodable {}
+protocol Service {
+\t// GET /search?{filter}
+\tfunc search(filter: Filter) async throws
+}
+";
+    let error = generation_error(source)?;
+    assert_eq!("Filter does not conform to Encodable", error.to_string());
+    assert_eq!(
+        (4, 14, 6),
+        (error.span().line, error.span().column, error.span().length)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn settings_applied() -> Result<()> {
+    let settings = Settings {
+        suffix: "Client".into(),
+        access: Some(AccessModifier::Public),
+        encoder: "JSONEncoder.api".into(),
+        decoder: "JSONDecoder.api".into(),
+        session: "session".into(),
+        headers: vec![
+            ("Accept".into(), "application/json".into()),
+            ("User-Agent".into(), "RestSwiftly".into()),
+        ],
+    };
+    let mut options = Options::default();
+    options.indent_width(2);
+
+    generated_file_correct_with("Configured", settings, &options)
+}
+
+fn generated_file_correct(name: &str) -> Result<()> {
+    generated_file_correct_with(name, Settings::default(), &Options::default())
+}
+
+fn generated_file_correct_with(name: &str, settings: Settings, options: &Options) -> Result<()> {
+    let input_file = File::open(format!("../samples/{name}.swift"))?;
+    let definitions = swift_parser::read_definitions(input_file)?;
+    let protocols: Vec<_> = definitions
+        .iter()
+        .filter_map(|d| match d {
+            Definition::Protocol {
+                name, definitions, ..
+            } => Some((name, definitions)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(1, protocols.len()); // sanity check, only expect one protocol per test
+    let (name, services) = protocols[0];
+
+    let suffix = settings.suffix.clone();
+    let class = Generator::new()
+        .set_settings(settings)
+        .add_models(&definitions)
+        .generate_service(name, services)?;
 
     let mut output = vec![];
-    class.generate(&mut output, &Options::default())?;
+    class.generate(&mut output, options)?;
 
     let output = from_utf8(&output)?;
-    let expected = fs::read(format!("../samples/outputs/{name}Impl.swift"))?;
+    let expected = fs::read(format!("../samples/outputs/{name}{suffix}.swift"))?;
     let expected = from_utf8(&expected)?;
 
     assert_eq!(expected, output, "{name} failed assertion.");
     Ok(())
 }
+
+/// Generates the `Service` protocol declared in `source` along with its models, expecting it to fail
+fn generation_error(source: &str) -> Result<GeneratingError> {
+    let definitions = swift_parser::read_definitions(source.as_bytes())?;
+    let services = match definitions.last() {
+        Some(Definition::Protocol { definitions, .. }) => definitions,
+        _ => panic!("Invalid test definition"),
+    };
+
+    let error = Generator::new()
+        .add_models(&definitions)
+        .generate_service("Service", services)
+        .err()
+        .expect("Expected failure");
+    error.downcast()
+}
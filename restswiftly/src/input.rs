@@ -0,0 +1,141 @@
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use anyhow::Result;
+use glob::glob;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum InputError {
+    #[error("No Swift files found for: {0}")]
+    NoMatches(String),
+    #[error("Invalid pattern {0}: {1}")]
+    InvalidPattern(String, glob::PatternError),
+}
+
+/// Swift source to read services from
+pub struct Input {
+    pub path: PathBuf,
+    /// Location relative to the closest directory containing all inputs, mirrored in the output
+    /// directory
+    pub relative: PathBuf,
+}
+
+/// Expands files, directories (recursively) and glob patterns into Swift sources
+pub fn collect_inputs(patterns: &[String]) -> Result<Vec<Input>> {
+    // Files with their location within the directory they were found in, and that directory
+    let mut found: Vec<(PathBuf, PathBuf, PathBuf)> = vec![];
+    for pattern in patterns {
+        let (root, paths) = if is_pattern(pattern) {
+            expand_pattern(pattern)?
+        } else {
+            let path = Path::new(pattern);
+            let root = if path.is_dir() {
+                path
+            } else {
+                path.parent().unwrap_or(path)
+            };
+            (root.to_path_buf(), expand_path(path)?)
+        };
+        if paths.is_empty() {
+            return Err(InputError::NoMatches(pattern.clone()).into());
+        }
+
+        // Canonical, so relative and absolute inputs share their ancestors
+        let directory = if root.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            root.as_path()
+        };
+        let directory = fs::canonicalize(directory)?;
+        for path in paths {
+            if found.iter().any(|(p, _, _)| *p == path) {
+                continue;
+            }
+            let within = match path.strip_prefix(&root) {
+                Ok(within) if !within.as_os_str().is_empty() => within.to_path_buf(),
+                _ => path.file_name().map(PathBuf::from).unwrap_or_default(),
+            };
+            found.push((path, within, directory.clone()));
+        }
+    }
+
+    // Mirrored from a shared directory, so files with the same name in different inputs don't collide
+    let common = common_ancestor(found.iter().map(|(_, _, directory)| directory.as_path()));
+    Ok(found
+        .into_iter()
+        .map(|(path, within, directory)| {
+            let relative = directory.strip_prefix(&common).unwrap_or(Path::new(""));
+            Input {
+                relative: relative.join(within),
+                path,
+            }
+        })
+        .collect())
+}
+
+fn is_pattern(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '['])
+}
+
+fn expand_pattern(pattern: &str) -> Result<(PathBuf, Vec<PathBuf>)> {
+    // Matches are mirrored from the last directory before the first wildcard
+    let root: PathBuf = Path::new(pattern)
+        .components()
+        .take_while(|c| !is_pattern(&c.as_os_str().to_string_lossy()))
+        .collect();
+
+    let paths = glob(pattern).map_err(|e| InputError::InvalidPattern(pattern.into(), e))?;
+    let mut files = vec![];
+    for path in paths {
+        let path = path?;
+        // Directories are searched, but other files only match when they are Swift sources
+        if path.is_dir() || is_swift(&path) {
+            files.extend(expand_path(&path)?);
+        }
+    }
+    Ok((root, files))
+}
+
+/// Lists `path` itself if it is a file, or Swift files within it if it is a directory
+fn expand_path(path: &Path) -> Result<Vec<PathBuf>> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let mut entries: Vec<_> = fs::read_dir(path)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<_, _>>()?;
+    entries.sort();
+
+    let mut files = vec![];
+    for entry in entries {
+        if entry.is_dir() || is_swift(&entry) {
+            files.extend(expand_path(&entry)?);
+        }
+    }
+    Ok(files)
+}
+
+fn is_swift(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e == "swift")
+}
+
+/// Longest path all of `paths` start with
+fn common_ancestor<'a>(mut paths: impl Iterator<Item = &'a Path>) -> PathBuf {
+    let first: Vec<Component> = match paths.next() {
+        Some(path) => path.components().collect(),
+        None => return PathBuf::new(),
+    };
+    let mut length = first.len();
+    for path in paths {
+        length = path
+            .components()
+            .zip(&first[..length])
+            .take_while(|(a, b)| a == *b)
+            .count();
+    }
+    first[..length].iter().collect()
+}
@@ -1,37 +1,215 @@
-use std::fs::File;
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, ErrorKind, Write},
+    path::{Path, PathBuf},
+    process,
+};
 
 use anyhow::Result;
 use args::Args;
 use clap::Parser;
-use generator::Generator;
-use swift_generator::Options;
-use swift_parser::Definition;
+use config::Config;
+use generator::{GeneratingError, Generator, Settings};
+use input::Input;
+use similar::TextDiff;
+use swift_generator::{ClassBuilder, Options};
+use swift_parser::{Definition, ParsingError, Span};
+use thiserror::Error;
 
 mod args;
+mod config;
 mod generator;
+mod input;
+#[cfg(test)]
+mod test;
+
+#[derive(Error, Debug)]
+pub enum OutputError {
+    #[error("{0} would be generated from both {1} and {2}")]
+    Duplicate(String, String, String),
+}
 
 fn main() -> Result<()> {
     env_logger::init();
 
     let args = Args::parse();
+    let config = Config::load(args.config.as_deref())?.merge(&args)?;
+
+    let inputs = input::collect_inputs(&config.inputs)?;
+    let mut sources = vec![];
+    for input in inputs {
+        let source = fs::read_to_string(&input.path)?;
+        sources.push((input, source));
+    }
 
-    let input_file = File::open(&args.file_name)?;
-    let definitions = swift_parser::read_definitions(input_file)?;
+    let outputs = match generate(&config, &sources) {
+        Ok(outputs) => outputs,
+        Err(error) => {
+            let source = error_span(&error).and_then(|span| {
+                let file = span.file.as_deref()?;
+                sources
+                    .iter()
+                    .find(|(input, _)| input.path.to_string_lossy() == file)
+                    .map(|(_, source)| (span, source))
+            });
+            match source {
+                Some((span, source)) => eprint!("{}", span.render(&error.to_string(), source)),
+                None => return Err(error),
+            }
+            process::exit(1);
+        }
+    };
+
+    if args.check {
+        let status = check(&outputs, &mut io::stdout())?;
+        if status != 0 {
+            process::exit(status);
+        }
+        return Ok(());
+    }
 
-    for definition in definitions.into_iter() {
-        generate_service(&args.output, &definition)?;
+    for (path, content) in outputs {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)?;
     }
 
     Ok(())
 }
 
-fn generate_service(out_file: &str, definition: &Definition) -> Result<()> {
+/// Generates all services, returning the files to write with their contents
+fn generate(config: &Config, sources: &[(Input, String)]) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+    let settings = config.settings();
+    let mut options = Options::default();
+    if let Some(indent) = config.indent {
+        options.indent_width(indent);
+    }
+
+    let mut files = vec![];
+    for (input, source) in sources {
+        let file_name = input.path.to_string_lossy();
+        let definitions = swift_parser::read_named_definitions(&file_name, source.as_bytes())?;
+        files.push((input, definitions));
+    }
+
+    let mut services = vec![];
+    for (input, definitions) in &files {
+        for definition in definitions {
+            if let Some((name, class)) = generate_service(definition, &files, &settings)? {
+                services.push((*input, name, class));
+            }
+        }
+    }
+
+    let mut outputs = vec![];
+    match &config.output_dir {
+        Some(output_dir) => {
+            let mut generated: HashMap<PathBuf, &Path> = HashMap::new();
+            for (input, name, class) in services {
+                let directory = input.relative.parent().unwrap_or_else(|| Path::new(""));
+                let path = Path::new(output_dir)
+                    .join(directory)
+                    .join(format!("{name}{}.swift", settings.suffix));
+                if let Some(previous) = generated.insert(path.clone(), &input.path) {
+                    return Err(OutputError::Duplicate(
+                        path.display().to_string(),
+                        previous.display().to_string(),
+                        input.path.display().to_string(),
+                    )
+                    .into());
+                }
+                let mut content = vec![];
+                class.generate(&mut content, &options)?;
+                outputs.push((path, content));
+            }
+        }
+        None => {
+            let mut content = vec![];
+            for (index, (_, _, class)) in services.iter().enumerate() {
+                if index > 0 {
+                    writeln!(content)?;
+                }
+                class.generate(&mut content, &options)?;
+            }
+            let output = config.output.as_deref().unwrap_or("out.swift");
+            outputs.push((PathBuf::from(output), content));
+        }
+    }
+
+    Ok(outputs)
+}
+
+/// Compares generated contents with the files on disk, writing a diff to `out` for every mismatch.
+/// Returns the exit status, non-zero when any file is out of date.
+fn check(outputs: &[(PathBuf, Vec<u8>)], out: &mut impl Write) -> Result<i32> {
+    let mut stale = 0;
+    for (path, content) in outputs {
+        if let Some(diff) = diff_output(path, content)? {
+            stale += 1;
+            write!(out, "{diff}")?;
+        }
+    }
+
+    if stale > 0 {
+        eprintln!(
+            "{stale} of {} generated files are out of date",
+            outputs.len()
+        );
+        return Ok(1);
+    }
+    Ok(0)
+}
+
+/// Unified diff from the file at `path` to `content`, missing files are compared as empty
+fn diff_output(path: &Path, content: &[u8]) -> Result<Option<String>> {
+    let expected = String::from_utf8_lossy(content);
+    let actual = match fs::read_to_string(path) {
+        Ok(actual) => actual,
+        Err(error) if error.kind() == ErrorKind::NotFound => String::new(),
+        Err(error) => return Err(error.into()),
+    };
+    if actual == expected {
+        return Ok(None);
+    }
+
+    let path = path.to_string_lossy();
+    let diff = TextDiff::from_lines(actual.as_str(), expected.as_ref());
+    Ok(Some(
+        diff.unified_diff()
+            .header(&format!("{path} (on disk)"), &format!("{path} (generated)"))
+            .to_string(),
+    ))
+}
+
+/// Generates the implementation of `definition` if it is a protocol, models can come from any file
+fn generate_service(
+    definition: &Definition,
+    files: &[(&Input, Vec<Definition>)],
+    settings: &Settings,
+) -> Result<Option<(String, ClassBuilder)>> {
     let (name, definitions) = match definition {
-        Definition::Protocol(name, definitions) => (name, definitions),
-        _ => return Ok(()),
+        Definition::Protocol {
+            name, definitions, ..
+        } => (name, definitions),
+        _ => return Ok(None),
     };
 
-    let class = Generator::new().generate_service(name, definitions)?;
+    let mut generator = Generator::new();
+    generator.set_settings(settings.clone());
+    for (_, models) in files {
+        generator.add_models(models);
+    }
+    let class = generator.generate_service(name, definitions)?;
 
-    class.generate(&mut File::create(out_file)?, &Options::default())
+    Ok(Some((name.clone(), class)))
+}
+
+/// Location of the error in the source, if it has one
+fn error_span(error: &anyhow::Error) -> Option<&Span> {
+    if let Some(error) = error.downcast_ref::<ParsingError>() {
+        return Some(error.span());
+    }
+    error.downcast_ref::<GeneratingError>().map(|e| e.span())
 }
@@ -0,0 +1,479 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use clap::Parser;
+use swift_generator::AccessModifier;
+use tempfile::TempDir;
+
+use crate::{
+    args::Args,
+    check,
+    config::{AccessLevel, Config, ConfigError},
+    generate,
+    input::{collect_inputs, Input, InputError},
+    OutputError,
+};
+
+const SERVICES: &str = "protocol Users {
+    // GET /users
+    func list() async throws -> [User]
+}
+
+protocol Roles {
+    // GET /roles
+    func list() async throws -> [Role]
+}
+";
+
+fn services_input() -> Vec<(Input, String)> {
+    let input = Input {
+        path: "Sources/Api/Services.swift".into(),
+        relative: "Api/Services.swift".into(),
+    };
+    vec![(input, SERVICES.into())]
+}
+
+#[test]
+fn services_are_generated_into_one_file() -> Result<()> {
+    let config = Config {
+        output: Some("Services.generated.swift".into()),
+        ..Config::default()
+    };
+
+    let outputs = generate(&config, &services_input())?;
+    let paths: Vec<_> = outputs.iter().map(|(path, _)| path.clone()).collect();
+    assert_eq!(vec![PathBuf::from("Services.generated.swift")], paths);
+
+    let content = String::from_utf8(outputs[0].1.clone())?;
+    let users = content
+        .find("class UsersImpl: Users {")
+        .expect("Users is generated");
+    let roles = content
+        .find("class RolesImpl: Roles {")
+        .expect("Roles is generated");
+    assert!(users < roles, "Services are generated in order");
+    assert!(content.contains("}\n\nclass RolesImpl"));
+    Ok(())
+}
+
+#[test]
+fn services_are_generated_into_own_files() -> Result<()> {
+    let config = Config {
+        output_dir: Some("Generated".into()),
+        suffix: Some("Client".into()),
+        ..Config::default()
+    };
+
+    let outputs = generate(&config, &services_input())?;
+    let paths: Vec<_> = outputs.iter().map(|(path, _)| path.clone()).collect();
+    assert_eq!(
+        vec![
+            PathBuf::from("Generated/Api/UsersClient.swift"),
+            PathBuf::from("Generated/Api/RolesClient.swift"),
+        ],
+        paths
+    );
+
+    let users = String::from_utf8(outputs[0].1.clone())?;
+    let roles = String::from_utf8(outputs[1].1.clone())?;
+    assert!(users.starts_with("class UsersClient: Users {"));
+    assert!(!users.contains("RolesClient"));
+    assert!(roles.starts_with("class RolesClient: Roles {"));
+    assert!(!roles.contains("UsersClient"));
+    Ok(())
+}
+
+#[test]
+fn services_must_not_share_output_file() -> Result<()> {
+    let config = Config {
+        output_dir: Some("Generated".into()),
+        ..Config::default()
+    };
+    let users = "protocol Users {
+    // GET /users
+    func list() async throws -> [User]
+}
+";
+    let sources: Vec<_> = ["Users.swift", "Admin.swift"]
+        .into_iter()
+        .map(|file| {
+            let input = Input {
+                path: Path::new("Sources/Api").join(file),
+                relative: Path::new("Api").join(file),
+            };
+            (input, users.to_string())
+        })
+        .collect();
+
+    let error = generate(&config, &sources).expect_err("Expected failure");
+    assert!(matches!(
+        error.downcast_ref::<OutputError>(),
+        Some(OutputError::Duplicate(..))
+    ));
+    assert_eq!(
+        "Generated/Api/UsersImpl.swift would be generated from both Sources/Api/Users.swift and Sources/Api/Admin.swift",
+        error.to_string()
+    );
+    Ok(())
+}
+
+#[test]
+fn combined_output_joins_service_files() -> Result<()> {
+    let combined = Config {
+        output: Some("Services.generated.swift".into()),
+        ..Config::default()
+    };
+    let separate = Config {
+        output_dir: Some("Generated".into()),
+        ..Config::default()
+    };
+
+    let combined = generate(&combined, &services_input())?;
+    let separate = generate(&separate, &services_input())?;
+    let joined = [separate[0].1.as_slice(), b"\n", separate[1].1.as_slice()].concat();
+    assert_eq!(
+        String::from_utf8(joined)?,
+        String::from_utf8(combined[0].1.clone())?
+    );
+    Ok(())
+}
+
+/// Creates `files` with empty contents inside a new temporary directory
+fn directory(files: &[&str]) -> Result<TempDir> {
+    let directory = tempfile::tempdir()?;
+    for file in files {
+        let path = directory.path().join(file);
+        fs::create_dir_all(path.parent().expect("Files have a parent"))?;
+        fs::write(path, "")?;
+    }
+    Ok(directory)
+}
+
+fn pattern(root: &Path, pattern: &str) -> String {
+    root.join(pattern).to_string_lossy().into_owned()
+}
+
+/// Paths and relative paths of the inputs found for `patterns`
+fn inputs(root: &Path, patterns: &[&str]) -> Result<Vec<(PathBuf, PathBuf)>> {
+    let patterns: Vec<_> = patterns.iter().map(|p| pattern(root, p)).collect();
+    Ok(collect_inputs(&patterns)?
+        .into_iter()
+        .map(|input| (input.path, input.relative))
+        .collect())
+}
+
+#[test]
+fn file_inputs_are_used_as_they_are() -> Result<()> {
+    let directory = directory(&["Services/Users.swift", "README.md"])?;
+    let root = directory.path();
+
+    assert_eq!(
+        inputs(root, &["Services/Users.swift"])?,
+        vec![(root.join("Services/Users.swift"), "Users.swift".into())]
+    );
+    Ok(())
+}
+
+#[test]
+fn directory_inputs_are_searched_for_swift_files() -> Result<()> {
+    let directory = directory(&[
+        "Services/Users.swift",
+        "Services/README.md",
+        "Services/Admin/Roles.swift",
+    ])?;
+    let root = directory.path();
+
+    assert_eq!(
+        inputs(root, &["Services"])?,
+        vec![
+            (
+                root.join("Services/Admin/Roles.swift"),
+                "Admin/Roles.swift".into()
+            ),
+            (root.join("Services/Users.swift"), "Users.swift".into()),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn pattern_inputs_only_match_swift_files() -> Result<()> {
+    let directory = directory(&[
+        "src/Users.swift",
+        "src/README.md",
+        "src/Admin/Roles.swift",
+        "src/Admin/notes.txt",
+    ])?;
+    let root = directory.path();
+
+    assert_eq!(
+        inputs(root, &["src/*"])?,
+        vec![
+            (
+                root.join("src/Admin/Roles.swift"),
+                "Admin/Roles.swift".into()
+            ),
+            (root.join("src/Users.swift"), "Users.swift".into()),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn inputs_are_mirrored_from_common_directory() -> Result<()> {
+    let directory = directory(&[
+        "Sources/Api/Users.swift",
+        "Sources/Admin/Users.swift",
+        "a/Roles.swift",
+        "b/Roles.swift",
+    ])?;
+    let root = directory.path();
+
+    assert_eq!(
+        inputs(root, &["Sources/Api", "Sources/Admin"])?,
+        vec![
+            (
+                root.join("Sources/Api/Users.swift"),
+                "Api/Users.swift".into()
+            ),
+            (
+                root.join("Sources/Admin/Users.swift"),
+                "Admin/Users.swift".into()
+            ),
+        ]
+    );
+    assert_eq!(
+        inputs(root, &["a/Roles.swift", "b/Roles.swift"])?,
+        vec![
+            (root.join("a/Roles.swift"), "a/Roles.swift".into()),
+            (root.join("b/Roles.swift"), "b/Roles.swift".into()),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn inputs_must_match_swift_files() -> Result<()> {
+    let directory = directory(&["src/README.md"])?;
+    let root = directory.path();
+
+    let error = inputs(root, &["src/*"]).expect_err("Nothing should match");
+    assert!(matches!(
+        error.downcast_ref::<InputError>(),
+        Some(InputError::NoMatches(_))
+    ));
+    Ok(())
+}
+
+/// Runs `check` on `outputs`, returning the exit status and the diff it printed
+fn checked(outputs: &[(PathBuf, Vec<u8>)]) -> Result<(i32, String)> {
+    let mut out = vec![];
+    let status = check(outputs, &mut out)?;
+    Ok((status, String::from_utf8(out)?))
+}
+
+#[test]
+fn check_passes_up_to_date_outputs() -> Result<()> {
+    let directory = tempfile::tempdir()?;
+    let path = directory.path().join("UsersImpl.swift");
+    fs::write(&path, "class UsersImpl {}\n")?;
+
+    let (status, diff) = checked(&[(path, b"class UsersImpl {}\n".to_vec())])?;
+    assert_eq!(0, status);
+    assert_eq!("", diff);
+    Ok(())
+}
+
+#[test]
+fn check_fails_stale_outputs() -> Result<()> {
+    let directory = tempfile::tempdir()?;
+    let current = directory.path().join("RolesImpl.swift");
+    let stale = directory.path().join("UsersImpl.swift");
+    fs::write(&current, "class RolesImpl {}\n")?;
+    fs::write(&stale, "class UsersImpl {}\n")?;
+
+    let (status, diff) = checked(&[
+        (current, b"class RolesImpl {}\n".to_vec()),
+        (stale.clone(), b"class UsersClient {}\n".to_vec()),
+    ])?;
+    let stale = stale.to_string_lossy();
+    assert_eq!(1, status);
+    assert_eq!(
+        format!(
+            "--- {stale} (on disk)\n+++ {stale} (generated)\n@@ -1 +1 @@\n-class UsersImpl {{}}\n+class UsersClient {{}}\n"
+        ),
+        diff
+    );
+    Ok(())
+}
+
+#[test]
+fn check_fails_missing_outputs() -> Result<()> {
+    let directory = tempfile::tempdir()?;
+    let missing = directory.path().join("UsersImpl.swift");
+
+    let (status, diff) = checked(&[(missing.clone(), b"class UsersImpl {}\n".to_vec())])?;
+    let missing = missing.to_string_lossy();
+    assert_eq!(1, status);
+    assert_eq!(
+        format!("--- {missing} (on disk)\n+++ {missing} (generated)\n@@ -0,0 +1 @@\n+class UsersImpl {{}}\n"),
+        diff
+    );
+    Ok(())
+}
+
+/// Writes `content` into a `restswiftly.toml` inside `directory` and loads it
+fn load_config(directory: &Path, content: &str) -> Result<Config> {
+    let path = directory.join("restswiftly.toml");
+    fs::write(&path, content)?;
+    Config::load(Some(&path.to_string_lossy()))
+}
+
+#[test]
+fn config_is_parsed() -> Result<()> {
+    let directory = tempfile::tempdir()?;
+    let config = load_config(
+        directory.path(),
+        r#"
+suffix = "Client"
+access = "public"
+indent = 2
+encoder = "JSONEncoder.api"
+decoder = "JSONDecoder.api"
+session = "session"
+
+[headers]
+Accept = "application/json"
+"#,
+    )?;
+
+    let settings = config.settings();
+    assert_eq!(Some(2), config.indent);
+    assert_eq!("Client", settings.suffix);
+    assert_eq!(Some(AccessModifier::Public), settings.access);
+    assert_eq!("JSONEncoder.api", settings.encoder);
+    assert_eq!("JSONDecoder.api", settings.decoder);
+    assert_eq!("session", settings.session);
+    assert_eq!(
+        vec![("Accept".to_string(), "application/json".to_string())],
+        settings.headers
+    );
+    Ok(())
+}
+
+#[test]
+fn config_paths_are_relative_to_file() -> Result<()> {
+    let directory = tempfile::tempdir()?;
+    let root = directory.path();
+    let config = load_config(
+        root,
+        r#"
+inputs = ["Sources/Services", "Sources/**/*Service.swift"]
+output-dir = "Generated"
+"#,
+    )?;
+
+    assert_eq!(
+        vec![
+            pattern(root, "Sources/Services"),
+            pattern(root, "Sources/**/*Service.swift")
+        ],
+        config.inputs
+    );
+    assert_eq!(Some(pattern(root, "Generated")), config.output_dir);
+    assert_eq!(None, config.output);
+    Ok(())
+}
+
+#[test]
+fn arguments_override_config() -> Result<()> {
+    let directory = tempfile::tempdir()?;
+    let config = load_config(
+        directory.path(),
+        r#"
+inputs = ["Sources"]
+output-dir = "Generated"
+suffix = "Client"
+access = "public"
+indent = 2
+"#,
+    )?;
+    let args = Args::parse_from([
+        "restswiftly",
+        "--file-name",
+        "Services.swift",
+        "--output",
+        "Services.generated.swift",
+        "--suffix",
+        "Api",
+        "--access",
+        "internal",
+        "--indent",
+        "4",
+    ]);
+
+    let config = config.merge(&args)?;
+    assert_eq!(vec!["Services.swift".to_string()], config.inputs);
+    assert_eq!(Some("Services.generated.swift".into()), config.output);
+    assert_eq!(None, config.output_dir);
+    assert_eq!(Some("Api".into()), config.suffix);
+    assert_eq!(Some(AccessLevel::Internal), config.access);
+    assert_eq!(Some(4), config.indent);
+    Ok(())
+}
+
+#[test]
+fn config_is_kept_without_arguments() -> Result<()> {
+    let directory = tempfile::tempdir()?;
+    let config = load_config(
+        directory.path(),
+        r#"
+inputs = ["Sources"]
+suffix = "Client"
+"#,
+    )?;
+
+    let config = config.merge(&Args::parse_from(["restswiftly"]))?;
+    assert_eq!(vec![pattern(directory.path(), "Sources")], config.inputs);
+    assert_eq!(Some("Client".into()), config.suffix);
+    Ok(())
+}
+
+#[test]
+fn unknown_config_fields_are_rejected() -> Result<()> {
+    let directory = tempfile::tempdir()?;
+    let error = load_config(directory.path(), r#"output-directory = "Generated""#)
+        .expect_err("Unknown fields should fail");
+
+    match error.downcast_ref::<ConfigError>() {
+        Some(ConfigError::Invalid(_, error)) => {
+            assert!(error
+                .to_string()
+                .contains("unknown field `output-directory`"))
+        }
+        _ => panic!("Unexpected error: {error}"),
+    }
+    Ok(())
+}
+
+#[test]
+fn config_outputs_must_not_conflict() -> Result<()> {
+    let directory = tempfile::tempdir()?;
+    let error = load_config(
+        directory.path(),
+        r#"
+output = "Services.generated.swift"
+output-dir = "Generated"
+"#,
+    )
+    .expect_err("Conflicting outputs should fail");
+
+    assert!(matches!(
+        error.downcast_ref::<ConfigError>(),
+        Some(ConfigError::ConflictingOutputs)
+    ));
+    Ok(())
+}
@@ -0,0 +1,13 @@
+struct User: Codable {
+	let name: String
+}
+
+protocol AnnotatedBody {
+	// POST /users
+	// Body: {user}
+	func create(user: User) async throws -> User
+
+	// PUT /users/{id}
+	// Body: {default}
+	func update(id: Int, with default: User) async throws
+}
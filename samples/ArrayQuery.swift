@@ -0,0 +1,4 @@
+protocol ArrayQuery {
+	// GET /search?tag=:tags&id=:ids(comma)&sort=:sort(pipe)&q=:words(space)&type=:types(brackets)
+	func search(tags: [String], ids: [Int], sort: [String], words: [String], types: [String]?) async throws
+}
@@ -0,0 +1,5 @@
+protocol Configured {
+	// POST /post
+	// Accept: text/plain
+	func post(body: Hello) async throws -> Hello
+}
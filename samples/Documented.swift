@@ -0,0 +1,17 @@
+/*
+ * Licensed under the Apache License, Version 2.0
+ * /* nested block comments are allowed */
+ */
+protocol Documented {
+	/// Fetches the greeting.
+	///
+	/// Returns nothing.
+	// GET /get
+	func get() async throws
+
+	/**
+	 * Posts a greeting.
+	 */
+	// POST /post
+	func post() async throws
+}
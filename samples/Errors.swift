@@ -0,0 +1,19 @@
+struct ApiError: Decodable {
+	let code: String
+	let message: String
+}
+
+struct LoginError: Codable {
+	let reason: String
+}
+
+protocol Errors {
+	// Error: ApiError
+
+	// GET /get
+	func get() async throws -> String
+
+	// POST /login
+	// Error: LoginError
+	func login(body: String) async throws
+}
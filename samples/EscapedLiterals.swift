@@ -0,0 +1,6 @@
+protocol EscapedLiterals {
+	// GET /search?q="swift"&raw=\(query)
+	// X-Quote: say "hi" \(not interpolated)
+	// X-Greeting: "Hello", {name}
+	func search(name: String) async throws
+}
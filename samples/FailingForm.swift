@@ -0,0 +1,15 @@
+struct Tags: Encodable {
+	let names: [String]
+	let visibility: Visibility
+}
+
+enum Visibility: Encodable {
+	case everyone
+	case friends
+}
+
+protocol FailingForm {
+	// POST /tags
+	// Content-Type: application/x-www-form-urlencoded
+	func post(body: Tags) async throws
+}
@@ -1,5 +1,26 @@
+struct Address: Encodable {
+	let street: String
+	let city: String
+}
+
+enum Plan: String, Encodable {
+	case free
+	case pro
+}
+
+struct Signup: Encodable {
+	let name: String
+	let plan: Plan
+	let tags: [String]
+	let addresses: [Address]
+}
+
 protocol FormEncoding {
 	// POST /post
     // Content-Type: application/x-www-form-urlencoded
 	func post(body: Hello) async throws
+
+	// POST /signup
+	// Content-Type: application/x-www-form-urlencoded
+	func signup(body: Signup) async throws
 }
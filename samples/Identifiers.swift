@@ -0,0 +1,8 @@
+protocol Identifiers {
+	// GET /users/{user_id}?default=:default&limit=:_limit
+	// X-Case: {case}
+	func get(_ user_id: String, for `default`: String, `in` _limit: String, `case`: String) async throws
+
+	// DELETE /users/{user_id}
+	func `repeat`(`var` user_id: String) async throws
+}
@@ -0,0 +1,12 @@
+protocol InterpolatedHeaders {
+	// GET /profile/{id}
+	// Authorization: Bearer {token}
+	// User-Agent: {app}/{version} (iOS)
+	func profile(id: Int, token: String, app: String, version: Int) async throws
+
+	// GET /sessions/{token}
+	// Authorization: Bearer {token}
+	// X-Token: {token}
+	// X-Client: {app}/{app}
+	func session(token: String, app: String) async throws
+}
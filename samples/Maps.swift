@@ -0,0 +1,9 @@
+protocol Maps {
+	// GET /search?q=:query&{filters*}
+	// Headers: {extraHeaders*}
+	func search(query: String, filters: [String: String], extraHeaders: [String: String]) async throws
+
+	// GET /counts?{limits*}
+	// Headers: {tracing*}
+	func counts(limits: [String: Int]?, tracing: [String: String]?) async throws
+}
@@ -0,0 +1,42 @@
+import Foundation
+import struct Foundation.UUID
+
+/// Greeting returned by the service
+struct Hello: Codable {
+    let id: UUID
+    var message: String = "Hello, \(name)!"
+    private(set) var count = 0x1F
+}
+
+enum Mood: String, Codable {
+    case happy = "happy"
+    case sad
+}
+
+extension Hello: Equatable {
+    static func == (lhs: Hello, rhs: Hello) -> Bool {
+        lhs.id == rhs.id && [1.5, 2e-3].count > 0
+    }
+}
+
+struct Credentials: Encodable {
+    let username: String
+    let password: String
+    var remember: Bool? = nil
+}
+
+typealias Greeting = Hello
+let defaultMood = Mood.happy // fallback
+
+@available(iOS 15, *)
+public protocol Models: AnyObject {
+    // GET /hello
+    func hello() async throws -> Hello
+
+    // POST /hello
+    func create(body: Hello) async throws -> [Greeting]
+
+    // POST /login
+    // Content-Type: application/x-www-form-urlencoded
+    func login(body: Credentials) async throws
+}
@@ -0,0 +1,17 @@
+struct Metadata: Encodable {
+	let title: String
+}
+
+enum Visibility: String {
+	case everyone
+	case friends
+}
+
+protocol MultipartParts {
+	// POST /users/{id}/avatar
+	// Part: avatar={image}
+	// Part: meta={metadata}
+	// Part: visibility={visibility}
+	// Part: caption={caption}
+	func upload(id: Int, image: MultipartFile, metadata: Metadata, visibility: Visibility, caption: String?) async throws
+}
@@ -0,0 +1,4 @@
+protocol OptionalQuery {
+	// GET /users?page=:page&limit=:limit&q=:query&default=:default
+	func users(page: Int, limit: Int?, query: String?, default: Bool?) async throws -> [User]
+}
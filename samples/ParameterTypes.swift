@@ -0,0 +1,27 @@
+enum Sort: String, Codable {
+	case name
+	case date
+}
+
+enum Priority: Int {
+	case low = 1
+	case high = 2
+}
+
+struct Version: CustomStringConvertible {
+	let major: Int
+	let minor: Int
+
+	var description: String {
+		"\(major).\(minor)"
+	}
+}
+
+protocol ParameterTypes {
+	// GET /items/{id}?limit=:limit&ratio=:ratio&active=:active&price=:price
+	func items(id: UUID, limit: Int, ratio: Double, active: Bool, price: Decimal) async throws
+
+	// GET /sorted?sort=:sort&priority=:priority
+	// Api-Version: {version}
+	func sorted(sort: Sort, priority: Priority, version: Version) async throws
+}
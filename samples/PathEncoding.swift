@@ -0,0 +1,4 @@
+protocol PathEncoding {
+	// GET /users/{id}/files/{path*}
+	func file(id: Int, path: String) async throws
+}
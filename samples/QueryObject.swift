@@ -0,0 +1,13 @@
+struct SearchFilter: Encodable {
+	let text: String
+	let minPrice: Double?
+	let tags: [String]
+}
+
+protocol QueryObject {
+	// GET /search?page=:page&{filter}
+	func search(page: Int, filter: SearchFilter) async throws -> [String]
+
+	// GET /filtered?{filter}
+	func filtered(filter: SearchFilter?) async throws
+}
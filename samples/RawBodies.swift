@@ -0,0 +1,22 @@
+protocol RawBodies {
+	// PUT /blobs/{id}
+	func upload(id: String, body: Data) async throws
+
+	// POST /notes
+	// Content-Type: text/plain; charset=utf-8
+	// Body: {text}
+	func note(text: String) async throws
+
+	// POST /avatar
+	// Content-Type: image/png
+	// Body: {file}
+	func avatar(file: URL) async throws
+
+	// PATCH /blobs/{id}
+	func replace(id: String, body: Data?) async throws
+
+	// POST /notes
+	// Content-Type: text/plain; charset=utf-8
+	// Body: {text}
+	func draft(text: String?) async throws
+}
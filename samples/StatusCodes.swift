@@ -0,0 +1,14 @@
+protocol StatusCodes {
+	// Success: 200-299
+
+	// GET /get
+	func get() async throws -> Hello
+
+	// POST /post
+	// Success: 201, 204
+	func post(body: Hello) async throws
+
+	// DELETE /delete
+	// Success: 204
+	func delete() async throws
+}
@@ -0,0 +1,19 @@
+protocol Types {
+	// GET /users
+	func list() async throws -> [User]
+
+	// GET /users/lookup
+	func lookup() async throws -> [String: User]
+
+	// GET /users/first
+	func first() async throws -> User?
+
+	// GET /users/page
+	func page() async throws -> Page<User>
+
+	// POST /users
+	func create(body: [Foundation.Data]) async throws -> [String: [User]]
+
+	// PUT /users
+	func update(body: some Encodable) async throws
+}
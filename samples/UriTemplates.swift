@@ -0,0 +1,24 @@
+enum Format: String {
+	case json
+	case xml
+}
+
+protocol UriTemplates {
+	// GET /repos/{owner}/{repo}/contents/{+path}
+	func contents(owner: String, repo: String, path: String) async throws
+
+	// GET /files{/segments*}{.format}
+	func file(segments: [String], format: Format?) async throws
+
+	// GET /search{?q,limit,tags*}
+	func search(q: String, limit: Int?, tags: [String]) async throws
+
+	// GET /items{;filters*}{?page}{&sort}
+	func items(filters: [String: Int], page: Int, sort: String?) async throws
+
+	// GET /users/{id}/avatar{?size}{#fragment}
+	func avatar(id: Int, size: Int?, fragment: String) async throws
+
+	// GET /prefix/{name:3}/{x}
+	func prefix(name: String, x: Int) async throws
+}
@@ -17,8 +17,9 @@ class AllMethodsImpl: AllMethods {
         request.httpMethod = "DELETE"
         let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
         let (data, response) = try await chain.proceed(with: request)
-        guard (response as? HTTPURLResponse)?.statusCode == 200 else {
-            fatalError("Unable to fetch data")
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
         }
         print(String(data: data, encoding: .utf8)!)
     }
@@ -29,8 +30,9 @@ class AllMethodsImpl: AllMethods {
         request.httpMethod = "GET"
         let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
         let (data, response) = try await chain.proceed(with: request)
-        guard (response as? HTTPURLResponse)?.statusCode == 200 else {
-            fatalError("Unable to fetch data")
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
         }
         print(String(data: data, encoding: .utf8)!)
     }
@@ -41,8 +43,9 @@ class AllMethodsImpl: AllMethods {
         request.httpMethod = "PATCH"
         let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
         let (data, response) = try await chain.proceed(with: request)
-        guard (response as? HTTPURLResponse)?.statusCode == 200 else {
-            fatalError("Unable to fetch data")
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
         }
         print(String(data: data, encoding: .utf8)!)
     }
@@ -53,8 +56,9 @@ class AllMethodsImpl: AllMethods {
         request.httpMethod = "POST"
         let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
         let (data, response) = try await chain.proceed(with: request)
-        guard (response as? HTTPURLResponse)?.statusCode == 200 else {
-            fatalError("Unable to fetch data")
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
         }
         print(String(data: data, encoding: .utf8)!)
     }
@@ -65,8 +69,9 @@ class AllMethodsImpl: AllMethods {
         request.httpMethod = "PUT"
         let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
         let (data, response) = try await chain.proceed(with: request)
-        guard (response as? HTTPURLResponse)?.statusCode == 200 else {
-            fatalError("Unable to fetch data")
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
         }
         print(String(data: data, encoding: .utf8)!)
     }
@@ -0,0 +1,45 @@
+class AnnotatedBodyImpl: AnnotatedBody {
+    private let baseUrl: String
+    private let interceptors: [Interceptor]
+
+    init(baseUrl: String, interceptors: Interceptor...) {
+        var baseUrl = baseUrl
+        if baseUrl.hasSuffix("/") {
+            baseUrl = String(baseUrl.removeLast())
+        }
+        self.baseUrl = baseUrl
+        self.interceptors = interceptors
+    }
+
+    func create(user: User) async throws -> User {
+        let url = URL(string: baseUrl + "/users")!
+        var request = URLRequest(url: url)
+        request.httpMethod = "POST"
+        let encoder = JSONEncoder()
+        request.httpBody = try encoder.encode(user)
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        let decoder = JSONDecoder()
+        return try decoder.decode(User.self, from: data)
+    }
+
+    func update(id: Int, with `default`: User) async throws {
+        let url = URL(string: baseUrl + "/users/{id}".replacingOccurrences(of: "{id}", with: String(id).addingPercentEncoding(withAllowedCharacters: .uriTemplateUnreserved)!))!
+        var request = URLRequest(url: url)
+        request.httpMethod = "PUT"
+        let encoder = JSONEncoder()
+        request.httpBody = try encoder.encode(`default`)
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+}
@@ -0,0 +1,42 @@
+class ArrayQueryImpl: ArrayQuery {
+    private let baseUrl: String
+    private let interceptors: [Interceptor]
+
+    init(baseUrl: String, interceptors: Interceptor...) {
+        var baseUrl = baseUrl
+        if baseUrl.hasSuffix("/") {
+            baseUrl = String(baseUrl.removeLast())
+        }
+        self.baseUrl = baseUrl
+        self.interceptors = interceptors
+    }
+
+    func search(tags: [String], ids: [Int], sort: [String], words: [String], types: [String]?) async throws {
+        var url = URL(string: baseUrl + "/search")!
+        var urlComponents = URLComponents(string: url.absoluteString)!
+        var queryItems = urlComponents.queryItems ?? []
+        for element in tags {
+            queryItems.append(URLQueryItem(name: "tag", value: element))
+        }
+        queryItems.append(URLQueryItem(name: "id", value: ids.map { element in String(element) }.joined(separator: ",")))
+        queryItems.append(URLQueryItem(name: "sort", value: sort.joined(separator: "|")))
+        queryItems.append(URLQueryItem(name: "q", value: words.joined(separator: " ")))
+        if let types = types {
+            for element in types {
+                queryItems.append(URLQueryItem(name: "type[]", value: element))
+            }
+        }
+        urlComponents.queryItems = queryItems
+        url = urlComponents.url!
+        var request = URLRequest(url: url)
+        request.httpMethod = "GET"
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+}
@@ -19,8 +19,9 @@ class BodyImpl: Body {
         request.httpBody = try encoder.encode(body)
         let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
         let (data, response) = try await chain.proceed(with: request)
-        guard (response as? HTTPURLResponse)?.statusCode == 200 else {
-            fatalError("Unable to fetch data")
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
         }
         print(String(data: data, encoding: .utf8)!)
     }
@@ -12,7 +12,7 @@ class CombinedImpl: Combined {
     }
 
     func post(for query: String, with path: String, body: Hello) async throws -> Hello {
-        var url = URL(string: baseUrl + "/{path}".replacingOccurrences(of: "{path}", with: path))!
+        var url = URL(string: baseUrl + "/{path}".replacingOccurrences(of: "{path}", with: path.addingPercentEncoding(withAllowedCharacters: .uriTemplateUnreserved)!))!
         var urlComponents = URLComponents(string: url.absoluteString)!
         var queryItems = urlComponents.queryItems ?? []
         queryItems.append(URLQueryItem(name: "q", value: query))
@@ -24,8 +24,9 @@ class CombinedImpl: Combined {
         request.httpBody = try encoder.encode(body)
         let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
         let (data, response) = try await chain.proceed(with: request)
-        guard (response as? HTTPURLResponse)?.statusCode == 200 else {
-            fatalError("Unable to fetch data")
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
         }
         let decoder = JSONDecoder()
         return try decoder.decode(Hello.self, from: data)
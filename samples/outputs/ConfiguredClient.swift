@@ -0,0 +1,32 @@
+public class ConfiguredClient: Configured {
+  private let baseUrl: String
+  private let interceptors: [Interceptor]
+
+  public init(baseUrl: String, interceptors: Interceptor...) {
+    var baseUrl = baseUrl
+    if baseUrl.hasSuffix("/") {
+      baseUrl = String(baseUrl.removeLast())
+    }
+    self.baseUrl = baseUrl
+    self.interceptors = interceptors
+  }
+
+  public func post(body: Hello) async throws -> Hello {
+    let url = URL(string: baseUrl + "/post")!
+    var request = URLRequest(url: url)
+    request.httpMethod = "POST"
+    request.addValue("RestSwiftly", forHTTPHeaderField: "User-Agent")
+    request.addValue("text/plain", forHTTPHeaderField: "Accept")
+    let encoder = JSONEncoder.api
+    request.httpBody = try encoder.encode(body)
+    let chain = Chain(using: interceptors) { session.data(for: request) }
+    let (data, response) = try await chain.proceed(with: request)
+    let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+    guard statusCode == 200 else {
+      throw HTTPError(response: response, body: data)
+    }
+    let decoder = JSONDecoder.api
+    return try decoder.decode(Hello.self, from: data)
+  }
+
+}
@@ -0,0 +1,44 @@
+class DocumentedImpl: Documented {
+    private let baseUrl: String
+    private let interceptors: [Interceptor]
+
+    init(baseUrl: String, interceptors: Interceptor...) {
+        var baseUrl = baseUrl
+        if baseUrl.hasSuffix("/") {
+            baseUrl = String(baseUrl.removeLast())
+        }
+        self.baseUrl = baseUrl
+        self.interceptors = interceptors
+    }
+
+    /// Fetches the greeting.
+    ///
+    /// Returns nothing.
+    func get() async throws {
+        let url = URL(string: baseUrl + "/get")!
+        var request = URLRequest(url: url)
+        request.httpMethod = "GET"
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+    /// Posts a greeting.
+    func post() async throws {
+        let url = URL(string: baseUrl + "/post")!
+        var request = URLRequest(url: url)
+        request.httpMethod = "POST"
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+}
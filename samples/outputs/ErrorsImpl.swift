@@ -0,0 +1,49 @@
+class ErrorsImpl: Errors {
+    private let baseUrl: String
+    private let interceptors: [Interceptor]
+
+    init(baseUrl: String, interceptors: Interceptor...) {
+        var baseUrl = baseUrl
+        if baseUrl.hasSuffix("/") {
+            baseUrl = String(baseUrl.removeLast())
+        }
+        self.baseUrl = baseUrl
+        self.interceptors = interceptors
+    }
+
+    func get() async throws -> String {
+        let url = URL(string: baseUrl + "/get")!
+        var request = URLRequest(url: url)
+        request.httpMethod = "GET"
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            if let error = try? JSONDecoder().decode(ApiError.self, from: data) {
+                throw TypedHTTPError(response: response, body: error)
+            }
+            throw HTTPError(response: response, body: data)
+        }
+        let decoder = JSONDecoder()
+        return try decoder.decode(String.self, from: data)
+    }
+
+    func login(body: String) async throws {
+        let url = URL(string: baseUrl + "/login")!
+        var request = URLRequest(url: url)
+        request.httpMethod = "POST"
+        request.addValue("application/octet-stream", forHTTPHeaderField: "Content-Type")
+        request.httpBody = Data(body.utf8)
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            if let error = try? JSONDecoder().decode(LoginError.self, from: data) {
+                throw TypedHTTPError(response: response, body: error)
+            }
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+}
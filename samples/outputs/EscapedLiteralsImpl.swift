@@ -0,0 +1,35 @@
+class EscapedLiteralsImpl: EscapedLiterals {
+    private let baseUrl: String
+    private let interceptors: [Interceptor]
+
+    init(baseUrl: String, interceptors: Interceptor...) {
+        var baseUrl = baseUrl
+        if baseUrl.hasSuffix("/") {
+            baseUrl = String(baseUrl.removeLast())
+        }
+        self.baseUrl = baseUrl
+        self.interceptors = interceptors
+    }
+
+    func search(name: String) async throws {
+        var url = URL(string: baseUrl + "/search")!
+        var urlComponents = URLComponents(string: url.absoluteString)!
+        var queryItems = urlComponents.queryItems ?? []
+        queryItems.append(URLQueryItem(name: "q", value: "\"swift\""))
+        queryItems.append(URLQueryItem(name: "raw", value: "\\(query)"))
+        urlComponents.queryItems = queryItems
+        url = urlComponents.url!
+        var request = URLRequest(url: url)
+        request.httpMethod = "GET"
+        request.addValue("say \"hi\" \\(not interpolated)", forHTTPHeaderField: "X-Quote")
+        request.addValue("\"Hello\", \(name)", forHTTPHeaderField: "X-Greeting")
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+}
@@ -20,8 +20,25 @@ class FormEncodingImpl: FormEncoding {
         request.httpBody = try encoder.encode(body)
         let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
         let (data, response) = try await chain.proceed(with: request)
-        guard (response as? HTTPURLResponse)?.statusCode == 200 else {
-            fatalError("Unable to fetch data")
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+    func signup(body: Signup) async throws {
+        let url = URL(string: baseUrl + "/signup")!
+        var request = URLRequest(url: url)
+        request.httpMethod = "POST"
+        request.addValue("application/x-www-form-urlencoded", forHTTPHeaderField: "Content-Type")
+        let encoder = FormEncoder()
+        request.httpBody = try encoder.encode(body)
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
         }
         print(String(data: data, encoding: .utf8)!)
     }
@@ -0,0 +1,47 @@
+class IdentifiersImpl: Identifiers {
+    private let baseUrl: String
+    private let interceptors: [Interceptor]
+
+    init(baseUrl: String, interceptors: Interceptor...) {
+        var baseUrl = baseUrl
+        if baseUrl.hasSuffix("/") {
+            baseUrl = String(baseUrl.removeLast())
+        }
+        self.baseUrl = baseUrl
+        self.interceptors = interceptors
+    }
+
+    func get(_ user_id: String, for `default`: String, in _limit: String, `case`: String) async throws {
+        var url = URL(string: baseUrl + "/users/{user_id}".replacingOccurrences(of: "{user_id}", with: user_id.addingPercentEncoding(withAllowedCharacters: .uriTemplateUnreserved)!))!
+        var urlComponents = URLComponents(string: url.absoluteString)!
+        var queryItems = urlComponents.queryItems ?? []
+        queryItems.append(URLQueryItem(name: "default", value: `default`))
+        queryItems.append(URLQueryItem(name: "limit", value: _limit))
+        urlComponents.queryItems = queryItems
+        url = urlComponents.url!
+        var request = URLRequest(url: url)
+        request.httpMethod = "GET"
+        request.addValue(`case`, forHTTPHeaderField: "X-Case")
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+    func `repeat`(`var` user_id: String) async throws {
+        let url = URL(string: baseUrl + "/users/{user_id}".replacingOccurrences(of: "{user_id}", with: user_id.addingPercentEncoding(withAllowedCharacters: .uriTemplateUnreserved)!))!
+        var request = URLRequest(url: url)
+        request.httpMethod = "DELETE"
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+}
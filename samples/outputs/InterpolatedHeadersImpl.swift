@@ -0,0 +1,45 @@
+class InterpolatedHeadersImpl: InterpolatedHeaders {
+    private let baseUrl: String
+    private let interceptors: [Interceptor]
+
+    init(baseUrl: String, interceptors: Interceptor...) {
+        var baseUrl = baseUrl
+        if baseUrl.hasSuffix("/") {
+            baseUrl = String(baseUrl.removeLast())
+        }
+        self.baseUrl = baseUrl
+        self.interceptors = interceptors
+    }
+
+    func profile(id: Int, token: String, app: String, version: Int) async throws {
+        let url = URL(string: baseUrl + "/profile/{id}".replacingOccurrences(of: "{id}", with: String(id).addingPercentEncoding(withAllowedCharacters: .uriTemplateUnreserved)!))!
+        var request = URLRequest(url: url)
+        request.httpMethod = "GET"
+        request.addValue("Bearer \(token)", forHTTPHeaderField: "Authorization")
+        request.addValue("\(app)/\(String(version)) (iOS)", forHTTPHeaderField: "User-Agent")
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+    func session(token: String, app: String) async throws {
+        let url = URL(string: baseUrl + "/sessions/{token}".replacingOccurrences(of: "{token}", with: token.addingPercentEncoding(withAllowedCharacters: .uriTemplateUnreserved)!))!
+        var request = URLRequest(url: url)
+        request.httpMethod = "GET"
+        request.addValue("Bearer \(token)", forHTTPHeaderField: "Authorization")
+        request.addValue(token, forHTTPHeaderField: "X-Token")
+        request.addValue("\(app)/\(app)", forHTTPHeaderField: "X-Client")
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+}
@@ -0,0 +1,65 @@
+class MapsImpl: Maps {
+    private let baseUrl: String
+    private let interceptors: [Interceptor]
+
+    init(baseUrl: String, interceptors: Interceptor...) {
+        var baseUrl = baseUrl
+        if baseUrl.hasSuffix("/") {
+            baseUrl = String(baseUrl.removeLast())
+        }
+        self.baseUrl = baseUrl
+        self.interceptors = interceptors
+    }
+
+    func search(query: String, filters: [String: String], extraHeaders: [String: String]) async throws {
+        var url = URL(string: baseUrl + "/search")!
+        var urlComponents = URLComponents(string: url.absoluteString)!
+        var queryItems = urlComponents.queryItems ?? []
+        queryItems.append(URLQueryItem(name: "q", value: query))
+        for (key, value) in filters.sorted(by: { $0.key < $1.key }) {
+            queryItems.append(URLQueryItem(name: key, value: value))
+        }
+        urlComponents.queryItems = queryItems
+        url = urlComponents.url!
+        var request = URLRequest(url: url)
+        request.httpMethod = "GET"
+        for (key, value) in extraHeaders.sorted(by: { $0.key < $1.key }) {
+            request.addValue(value, forHTTPHeaderField: key)
+        }
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+    func counts(limits: [String: Int]?, tracing: [String: String]?) async throws {
+        var url = URL(string: baseUrl + "/counts")!
+        var urlComponents = URLComponents(string: url.absoluteString)!
+        var queryItems = urlComponents.queryItems ?? []
+        if let limits = limits {
+            for (key, value) in limits.sorted(by: { $0.key < $1.key }) {
+                queryItems.append(URLQueryItem(name: key, value: String(value)))
+            }
+        }
+        urlComponents.queryItems = queryItems
+        url = urlComponents.url!
+        var request = URLRequest(url: url)
+        request.httpMethod = "GET"
+        if let tracing = tracing {
+            for (key, value) in tracing.sorted(by: { $0.key < $1.key }) {
+                request.addValue(value, forHTTPHeaderField: key)
+            }
+        }
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+}
@@ -0,0 +1,38 @@
+class MultipartPartsImpl: MultipartParts {
+    private let baseUrl: String
+    private let interceptors: [Interceptor]
+
+    init(baseUrl: String, interceptors: Interceptor...) {
+        var baseUrl = baseUrl
+        if baseUrl.hasSuffix("/") {
+            baseUrl = String(baseUrl.removeLast())
+        }
+        self.baseUrl = baseUrl
+        self.interceptors = interceptors
+    }
+
+    func upload(id: Int, image: MultipartFile, metadata: Metadata, visibility: Visibility, caption: String?) async throws {
+        let url = URL(string: baseUrl + "/users/{id}/avatar".replacingOccurrences(of: "{id}", with: String(id).addingPercentEncoding(withAllowedCharacters: .uriTemplateUnreserved)!))!
+        var request = URLRequest(url: url)
+        request.httpMethod = "POST"
+        let boundary = UUID().uuidString
+        request.addValue("multipart/form-data; boundary=\(boundary)", forHTTPHeaderField: "Content-Type")
+        let encoder = JSONEncoder()
+        var parts = MultipartBody(boundary: boundary)
+        parts.add(name: "avatar", file: image)
+        parts.add(name: "meta", json: try encoder.encode(metadata))
+        parts.add(name: "visibility", value: visibility.rawValue)
+        if let caption = caption {
+            parts.add(name: "caption", value: caption)
+        }
+        request.httpBody = parts.build()
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+}
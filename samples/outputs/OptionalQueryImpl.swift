@@ -0,0 +1,42 @@
+class OptionalQueryImpl: OptionalQuery {
+    private let baseUrl: String
+    private let interceptors: [Interceptor]
+
+    init(baseUrl: String, interceptors: Interceptor...) {
+        var baseUrl = baseUrl
+        if baseUrl.hasSuffix("/") {
+            baseUrl = String(baseUrl.removeLast())
+        }
+        self.baseUrl = baseUrl
+        self.interceptors = interceptors
+    }
+
+    func users(page: Int, limit: Int?, query: String?, `default`: Bool?) async throws -> [User] {
+        var url = URL(string: baseUrl + "/users")!
+        var urlComponents = URLComponents(string: url.absoluteString)!
+        var queryItems = urlComponents.queryItems ?? []
+        queryItems.append(URLQueryItem(name: "page", value: String(page)))
+        if let limit = limit {
+            queryItems.append(URLQueryItem(name: "limit", value: String(limit)))
+        }
+        if let query = query {
+            queryItems.append(URLQueryItem(name: "q", value: query))
+        }
+        if let `default` = `default` {
+            queryItems.append(URLQueryItem(name: "default", value: String(`default`)))
+        }
+        urlComponents.queryItems = queryItems
+        url = urlComponents.url!
+        var request = URLRequest(url: url)
+        request.httpMethod = "GET"
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        let decoder = JSONDecoder()
+        return try decoder.decode([User].self, from: data)
+    }
+
+}
@@ -0,0 +1,55 @@
+class ParameterTypesImpl: ParameterTypes {
+    private let baseUrl: String
+    private let interceptors: [Interceptor]
+
+    init(baseUrl: String, interceptors: Interceptor...) {
+        var baseUrl = baseUrl
+        if baseUrl.hasSuffix("/") {
+            baseUrl = String(baseUrl.removeLast())
+        }
+        self.baseUrl = baseUrl
+        self.interceptors = interceptors
+    }
+
+    func items(id: UUID, limit: Int, ratio: Double, active: Bool, price: Decimal) async throws {
+        var url = URL(string: baseUrl + "/items/{id}".replacingOccurrences(of: "{id}", with: id.uuidString.addingPercentEncoding(withAllowedCharacters: .uriTemplateUnreserved)!))!
+        var urlComponents = URLComponents(string: url.absoluteString)!
+        var queryItems = urlComponents.queryItems ?? []
+        queryItems.append(URLQueryItem(name: "limit", value: String(limit)))
+        queryItems.append(URLQueryItem(name: "ratio", value: String(ratio)))
+        queryItems.append(URLQueryItem(name: "active", value: String(active)))
+        queryItems.append(URLQueryItem(name: "price", value: price.description))
+        urlComponents.queryItems = queryItems
+        url = urlComponents.url!
+        var request = URLRequest(url: url)
+        request.httpMethod = "GET"
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+    func sorted(sort: Sort, priority: Priority, version: Version) async throws {
+        var url = URL(string: baseUrl + "/sorted")!
+        var urlComponents = URLComponents(string: url.absoluteString)!
+        var queryItems = urlComponents.queryItems ?? []
+        queryItems.append(URLQueryItem(name: "sort", value: sort.rawValue))
+        queryItems.append(URLQueryItem(name: "priority", value: String(priority.rawValue)))
+        urlComponents.queryItems = queryItems
+        url = urlComponents.url!
+        var request = URLRequest(url: url)
+        request.httpMethod = "GET"
+        request.addValue(version.description, forHTTPHeaderField: "Api-Version")
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+}
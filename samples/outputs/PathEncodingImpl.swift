@@ -0,0 +1,27 @@
+class PathEncodingImpl: PathEncoding {
+    private let baseUrl: String
+    private let interceptors: [Interceptor]
+
+    init(baseUrl: String, interceptors: Interceptor...) {
+        var baseUrl = baseUrl
+        if baseUrl.hasSuffix("/") {
+            baseUrl = String(baseUrl.removeLast())
+        }
+        self.baseUrl = baseUrl
+        self.interceptors = interceptors
+    }
+
+    func file(id: Int, path: String) async throws {
+        let url = URL(string: baseUrl + "/users/{id}/files/{path*}".replacingOccurrences(of: "{id}", with: String(id).addingPercentEncoding(withAllowedCharacters: .uriTemplateUnreserved)!).replacingOccurrences(of: "{path*}", with: path.addingPercentEncoding(withAllowedCharacters: .uriTemplatePath)!))!
+        var request = URLRequest(url: url)
+        request.httpMethod = "GET"
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+}
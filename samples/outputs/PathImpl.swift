@@ -12,13 +12,14 @@ class PathImpl: Path {
     }
 
     func get(path: String) async throws {
-        let url = URL(string: baseUrl + "/{path}/get".replacingOccurrences(of: "{path}", with: path))!
+        let url = URL(string: baseUrl + "/{path}/get".replacingOccurrences(of: "{path}", with: path.addingPercentEncoding(withAllowedCharacters: .uriTemplateUnreserved)!))!
         var request = URLRequest(url: url)
         request.httpMethod = "GET"
         let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
         let (data, response) = try await chain.proceed(with: request)
-        guard (response as? HTTPURLResponse)?.statusCode == 200 else {
-            fatalError("Unable to fetch data")
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
         }
         print(String(data: data, encoding: .utf8)!)
     }
@@ -0,0 +1,92 @@
+class RawBodiesImpl: RawBodies {
+    private let baseUrl: String
+    private let interceptors: [Interceptor]
+
+    init(baseUrl: String, interceptors: Interceptor...) {
+        var baseUrl = baseUrl
+        if baseUrl.hasSuffix("/") {
+            baseUrl = String(baseUrl.removeLast())
+        }
+        self.baseUrl = baseUrl
+        self.interceptors = interceptors
+    }
+
+    func upload(id: String, body: Data) async throws {
+        let url = URL(string: baseUrl + "/blobs/{id}".replacingOccurrences(of: "{id}", with: id.addingPercentEncoding(withAllowedCharacters: .uriTemplateUnreserved)!))!
+        var request = URLRequest(url: url)
+        request.httpMethod = "PUT"
+        request.addValue("application/octet-stream", forHTTPHeaderField: "Content-Type")
+        request.httpBody = body
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+    func note(text: String) async throws {
+        let url = URL(string: baseUrl + "/notes")!
+        var request = URLRequest(url: url)
+        request.httpMethod = "POST"
+        request.addValue("text/plain; charset=utf-8", forHTTPHeaderField: "Content-Type")
+        request.httpBody = Data(text.utf8)
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+    func avatar(file: URL) async throws {
+        let url = URL(string: baseUrl + "/avatar")!
+        var request = URLRequest(url: url)
+        request.httpMethod = "POST"
+        request.addValue("image/png", forHTTPHeaderField: "Content-Type")
+        let chain = Chain(using: interceptors) { URLSession.shared.upload(for: request, fromFile: file) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+    func replace(id: String, body: Data?) async throws {
+        let url = URL(string: baseUrl + "/blobs/{id}".replacingOccurrences(of: "{id}", with: id.addingPercentEncoding(withAllowedCharacters: .uriTemplateUnreserved)!))!
+        var request = URLRequest(url: url)
+        request.httpMethod = "PATCH"
+        request.addValue("application/octet-stream", forHTTPHeaderField: "Content-Type")
+        if let body = body {
+            request.httpBody = body
+        }
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+    func draft(text: String?) async throws {
+        let url = URL(string: baseUrl + "/notes")!
+        var request = URLRequest(url: url)
+        request.httpMethod = "POST"
+        request.addValue("text/plain; charset=utf-8", forHTTPHeaderField: "Content-Type")
+        if let text = text {
+            request.httpBody = Data(text.utf8)
+        }
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+}
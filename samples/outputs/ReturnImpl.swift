@@ -17,8 +17,9 @@ class ReturnImpl: Return {
         request.httpMethod = "GET"
         let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
         let (data, response) = try await chain.proceed(with: request)
-        guard (response as? HTTPURLResponse)?.statusCode == 200 else {
-            fatalError("Unable to fetch data")
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
         }
         let decoder = JSONDecoder()
         return try decoder.decode(Hello.self, from: data)
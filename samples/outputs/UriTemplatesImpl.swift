@@ -0,0 +1,92 @@
+class UriTemplatesImpl: UriTemplates {
+    private let baseUrl: String
+    private let interceptors: [Interceptor]
+
+    init(baseUrl: String, interceptors: Interceptor...) {
+        var baseUrl = baseUrl
+        if baseUrl.hasSuffix("/") {
+            baseUrl = String(baseUrl.removeLast())
+        }
+        self.baseUrl = baseUrl
+        self.interceptors = interceptors
+    }
+
+    func contents(owner: String, repo: String, path: String) async throws {
+        let url = URL(string: baseUrl + "/repos/{owner}/{repo}/contents/{+path}".replacingOccurrences(of: "{owner}", with: owner.addingPercentEncoding(withAllowedCharacters: .uriTemplateUnreserved)!).replacingOccurrences(of: "{repo}", with: repo.addingPercentEncoding(withAllowedCharacters: .uriTemplateUnreserved)!).replacingOccurrences(of: "{+path}", with: path.addingPercentEncoding(withAllowedCharacters: .uriTemplateReserved)!))!
+        var request = URLRequest(url: url)
+        request.httpMethod = "GET"
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+    func file(segments: [String], format: Format?) async throws {
+        let url = URL(string: baseUrl + "/files{/segments*}{.format}".replacingOccurrences(of: "{/segments*}", with: URITemplate.expand("/", [.init("segments", .list(segments), explode: true)])).replacingOccurrences(of: "{.format}", with: URITemplate.expand(".", [.init("format", format.map { format in .string(format.rawValue) })])))!
+        var request = URLRequest(url: url)
+        request.httpMethod = "GET"
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+    func search(q: String, limit: Int?, tags: [String]) async throws {
+        let url = URL(string: baseUrl + "/search{?q,limit,tags*}".replacingOccurrences(of: "{?q,limit,tags*}", with: URITemplate.expand("?", [.init("q", .string(q)), .init("limit", limit.map { limit in .string(String(limit)) }), .init("tags", .list(tags), explode: true)])))!
+        var request = URLRequest(url: url)
+        request.httpMethod = "GET"
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+    func items(filters: [String: Int], page: Int, sort: String?) async throws {
+        let url = URL(string: baseUrl + "/items{;filters*}{?page}{&sort}".replacingOccurrences(of: "{;filters*}", with: URITemplate.expand(";", [.init("filters", .pairs(filters.sorted(by: { $0.key < $1.key }).map { key, value in (key, String(value)) }), explode: true)])).replacingOccurrences(of: "{?page}", with: URITemplate.expand("?", [.init("page", .string(String(page)))])).replacingOccurrences(of: "{&sort}", with: URITemplate.expand("&", [.init("sort", sort.map { sort in .string(sort) })])))!
+        var request = URLRequest(url: url)
+        request.httpMethod = "GET"
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+    func avatar(id: Int, size: Int?, fragment: String) async throws {
+        let url = URL(string: baseUrl + "/users/{id}/avatar{?size}{#fragment}".replacingOccurrences(of: "{id}", with: String(id).addingPercentEncoding(withAllowedCharacters: .uriTemplateUnreserved)!).replacingOccurrences(of: "{?size}", with: URITemplate.expand("?", [.init("size", size.map { size in .string(String(size)) })])).replacingOccurrences(of: "{#fragment}", with: URITemplate.expand("#", [.init("fragment", .string(fragment))])))!
+        var request = URLRequest(url: url)
+        request.httpMethod = "GET"
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+    func prefix(name: String, x: Int) async throws {
+        let url = URL(string: baseUrl + "/prefix/{name:3}/{x}".replacingOccurrences(of: "{name:3}", with: URITemplate.expand("", [.init("name", .string(name), prefix: 3)])).replacingOccurrences(of: "{x}", with: String(x).addingPercentEncoding(withAllowedCharacters: .uriTemplateUnreserved)!))!
+        var request = URLRequest(url: url)
+        request.httpMethod = "GET"
+        let chain = Chain(using: interceptors) { URLSession.shared.data(for: request) }
+        let (data, response) = try await chain.proceed(with: request)
+        let statusCode = (response as? HTTPURLResponse)?.statusCode ?? 0
+        guard statusCode == 200 else {
+            throw HTTPError(response: response, body: data)
+        }
+        print(String(data: data, encoding: .utf8)!)
+    }
+
+}
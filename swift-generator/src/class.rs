@@ -2,9 +2,12 @@ use std::io::Write;
 
 use anyhow::Result;
 
-use crate::{field::FieldBuilder, write_indent, writeln_indent, FunctionBuilder, Options};
+use crate::{
+    field::FieldBuilder, write_indent, writeln_indent, AccessModifier, FunctionBuilder, Options,
+};
 
 pub struct ClassBuilder {
+    modifier: Option<AccessModifier>,
     name: String,
     fields: Vec<FieldBuilder>,
     functions: Vec<FunctionBuilder>,
@@ -14,6 +17,7 @@ pub struct ClassBuilder {
 impl ClassBuilder {
     pub fn new(name: &str) -> ClassBuilder {
         ClassBuilder {
+            modifier: None,
             fields: vec![],
             name: name.to_owned(),
             functions: vec![],
@@ -21,6 +25,11 @@ impl ClassBuilder {
         }
     }
 
+    pub fn set_modifier(&mut self, modifier: AccessModifier) -> &mut Self {
+        self.modifier = Some(modifier);
+        self
+    }
+
     pub fn add_super(&mut self, super_type: &str) -> &mut Self {
         self.supers.push(super_type.to_owned());
         self
@@ -47,11 +56,11 @@ impl ClassBuilder {
         let indent = options.indent.unwrap_or(0);
 
         self.generate_start(writer, indent)?;
-        self.generate_fields(writer, indent)?;
+        self.generate_fields(writer, options)?;
         if !self.fields.is_empty() {
             writeln!(writer)?;
         }
-        self.generate_functions(writer, indent)?;
+        self.generate_functions(writer, options)?;
 
         writeln_indent!(writer, indent, "}}")?;
 
@@ -59,25 +68,28 @@ impl ClassBuilder {
     }
 
     fn generate_start(&self, writer: &mut impl Write, indent: u8) -> Result<()> {
-        write_indent!(writer, indent, "class {}", self.name)?;
+        write_indent!(writer, indent)?;
+        if let Some(modifier) = &self.modifier {
+            write!(writer, "{} ", modifier.keyword())?;
+        }
+        write!(writer, "class {}", self.name)?;
         if !self.supers.is_empty() {
-            write_indent!(writer, indent, ": ")?;
-            write_indent!(writer, indent, "{}", self.supers.join(", "))?;
+            write!(writer, ": {}", self.supers.join(", "))?;
         }
-        writeln_indent!(writer, indent, " {{")?;
+        writeln!(writer, " {{")?;
         Ok(())
     }
 
-    fn generate_fields(&self, writer: &mut impl Write, indent: u8) -> Result<()> {
+    fn generate_fields(&self, writer: &mut impl Write, options: &Options) -> Result<()> {
         for field in &self.fields {
-            field.generate(writer, Options::default().indent(indent + 4))?;
+            field.generate(writer, &options.nested())?;
         }
         Ok(())
     }
 
-    fn generate_functions(&self, writer: &mut impl Write, indent: u8) -> Result<()> {
+    fn generate_functions(&self, writer: &mut impl Write, options: &Options) -> Result<()> {
         for function in &self.functions {
-            function.generate(writer, Options::default().indent(indent + 4))?;
+            function.generate(writer, &options.nested())?;
             writeln!(writer)?;
         }
         Ok(())
@@ -1,5 +1,4 @@
 use crate::Options;
-use crate::DEFAULT_INDENT;
 
 use std::io::Write;
 
@@ -22,6 +21,7 @@ impl CodeBuilder {
         let start = match control_type {
             ControlType::If => format!("if {condition} {{"),
             ControlType::Guard => format!("guard {condition} else {{"),
+            ControlType::For => format!("for {condition} {{"),
         };
 
         self.lines.push(Code::ControlFlow {
@@ -45,7 +45,7 @@ impl CodeBuilder {
                 Code::Line(line) => writeln_indent!(writer, indent, "{line}")?,
                 Code::ControlFlow { start, end, code } => {
                     writeln_indent!(writer, indent, "{start}")?;
-                    code.generate(writer, Options::default().indent(indent + DEFAULT_INDENT))?;
+                    code.generate(writer, &options.nested())?;
                     writeln_indent!(writer, indent, "{end}")?;
                 }
             }
@@ -57,6 +57,8 @@ impl CodeBuilder {
 pub enum ControlType {
     If,
     Guard,
+    /// Loop where the condition is the `element in sequence` part
+    For,
 }
 
 enum Code {
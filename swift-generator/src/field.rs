@@ -15,13 +15,7 @@ impl FieldBuilder {
         let indent = options.indent.unwrap_or(DEFAULT_INDENT);
 
         if let Some(modifier) = &self.modifier {
-            let modifier = match modifier {
-                AccessModifier::FilePrivate => "fileprivate",
-                AccessModifier::Internal => "internal",
-                AccessModifier::Private => "private",
-                AccessModifier::Public => "public",
-            };
-            write_indent!(writer, indent, "{modifier} ")?;
+            write_indent!(writer, indent, "{} ", modifier.keyword())?;
         } else {
             write_indent!(writer, indent)?;
         }
@@ -2,29 +2,41 @@ use std::io::Write;
 
 use anyhow::Result;
 
-use crate::{write_indent, writeln_indent, CodeBuilder, Options, ParameterBuilder, DEFAULT_INDENT};
+use crate::{
+    escape_identifier, write_indent, writeln_indent, AccessModifier, CodeBuilder, Options,
+    ParameterBuilder,
+};
 
 pub struct FunctionBuilder {
+    modifier: Option<AccessModifier>,
     name: String,
     parameters: Vec<ParameterBuilder>,
     code: Vec<CodeBuilder>,
     is_async: bool,
     is_throws: bool,
     return_type: Option<String>,
+    documentation: Vec<String>,
 }
 
 impl FunctionBuilder {
     pub fn new(name: &str) -> FunctionBuilder {
         FunctionBuilder {
+            modifier: None,
             name: name.to_owned(),
             parameters: vec![],
             code: vec![],
             is_async: false,
             is_throws: false,
             return_type: None,
+            documentation: vec![],
         }
     }
 
+    pub fn set_modifier(&mut self, modifier: AccessModifier) -> &mut Self {
+        self.modifier = Some(modifier);
+        self
+    }
+
     pub fn add_parameter(&mut self, parameter: ParameterBuilder) -> &mut Self {
         self.parameters.push(parameter);
         self
@@ -50,15 +62,32 @@ impl FunctionBuilder {
         self
     }
 
+    pub fn add_documentation(&mut self, line: &str) -> &mut Self {
+        self.documentation.push(line.into());
+        self
+    }
+
     pub fn generate(&self, writer: &mut impl Write, options: &Options) -> Result<()> {
         let indent = options.indent.unwrap_or(0);
 
+        for line in &self.documentation {
+            if line.is_empty() {
+                writeln_indent!(writer, indent, "///")?;
+            } else {
+                writeln_indent!(writer, indent, "/// {line}")?;
+            }
+        }
+
+        write_indent!(writer, indent)?;
+        if let Some(modifier) = &self.modifier {
+            write!(writer, "{} ", modifier.keyword())?;
+        }
         if self.name != "init" {
-            write_indent!(writer, indent, "func {}(", self.name)?;
+            write!(writer, "func {}(", escape_identifier(&self.name))?;
             self.generate_parameters(writer)?;
             write!(writer, ") ")?;
         } else {
-            write_indent!(writer, indent, "init(")?;
+            write!(writer, "init(")?;
             self.generate_parameters(writer)?;
             write!(writer, ") ")?;
         }
@@ -74,7 +103,7 @@ impl FunctionBuilder {
         writeln!(writer, "{{")?;
 
         for statement in &self.code {
-            statement.generate(writer, Options::default().indent(indent + DEFAULT_INDENT))?;
+            statement.generate(writer, &options.nested())?;
         }
 
         writeln_indent!(writer, indent, "}}")?;
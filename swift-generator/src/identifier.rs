@@ -0,0 +1,76 @@
+/// Keywords that can only be used as identifiers when escaped with backticks
+const KEYWORDS: [&str; 52] = [
+    "Any",
+    "Self",
+    "as",
+    "associatedtype",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "continue",
+    "default",
+    "defer",
+    "deinit",
+    "do",
+    "else",
+    "enum",
+    "extension",
+    "fallthrough",
+    "false",
+    "fileprivate",
+    "for",
+    "func",
+    "guard",
+    "if",
+    "import",
+    "in",
+    "init",
+    "inout",
+    "internal",
+    "is",
+    "let",
+    "nil",
+    "operator",
+    "precedencegroup",
+    "private",
+    "protocol",
+    "public",
+    "repeat",
+    "rethrows",
+    "return",
+    "self",
+    "static",
+    "struct",
+    "subscript",
+    "super",
+    "switch",
+    "throw",
+    "throws",
+    "true",
+    "try",
+    "var",
+    "where",
+    "while",
+];
+
+/// Keywords that can't be used as argument labels without backticks
+const LABEL_KEYWORDS: [&str; 3] = ["inout", "let", "var"];
+
+/// Escapes `name` with backticks if it is a keyword, `default` becomes `` `default` ``
+pub fn escape_identifier(name: &str) -> String {
+    if KEYWORDS.contains(&name) {
+        format!("`{name}`")
+    } else {
+        name.to_owned()
+    }
+}
+
+/// Escapes an argument label, where most keywords are allowed as they are
+pub fn escape_label(label: &str) -> String {
+    if LABEL_KEYWORDS.contains(&label) {
+        format!("`{label}`")
+    } else {
+        label.to_owned()
+    }
+}
@@ -11,6 +11,15 @@ pub use field::FieldBuilder;
 mod function;
 pub use function::FunctionBuilder;
 
+mod identifier;
+pub use identifier::escape_identifier;
+pub use identifier::escape_label;
+
+mod literal;
+pub use literal::escape_string;
+pub use literal::string_literal;
+pub use literal::StringLiteral;
+
 mod parameter;
 pub use parameter::ParameterBuilder;
 
@@ -18,9 +27,10 @@ mod macros;
 
 pub(crate) const DEFAULT_INDENT: u8 = 4;
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct Options {
     indent: Option<u8>,
+    indent_width: Option<u8>,
 }
 
 impl Options {
@@ -28,11 +38,38 @@ impl Options {
         self.indent = Some(count);
         self
     }
+
+    /// Number of spaces each nesting level is indented by
+    pub fn indent_width(&mut self, width: u8) -> &mut Self {
+        self.indent_width = Some(width);
+        self
+    }
+
+    /// Options for code nested one level deeper
+    pub(crate) fn nested(&self) -> Options {
+        let width = self.indent_width.unwrap_or(DEFAULT_INDENT);
+        Options {
+            indent: Some(self.indent.unwrap_or(0) + width),
+            indent_width: self.indent_width,
+        }
+    }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum AccessModifier {
     Public,
     Internal,
     FilePrivate,
     Private,
 }
+
+impl AccessModifier {
+    pub(crate) fn keyword(&self) -> &'static str {
+        match self {
+            AccessModifier::FilePrivate => "fileprivate",
+            AccessModifier::Internal => "internal",
+            AccessModifier::Private => "private",
+            AccessModifier::Public => "public",
+        }
+    }
+}
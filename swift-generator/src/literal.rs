@@ -0,0 +1,56 @@
+use std::fmt::{self, Display, Formatter, Write};
+
+/// Swift string literal, text is escaped while expressions are interpolated as `\(expression)`
+#[derive(Clone, Debug, Default)]
+pub struct StringLiteral {
+    contents: String,
+}
+
+impl StringLiteral {
+    pub fn new(text: &str) -> Self {
+        let mut literal = StringLiteral::default();
+        literal.add_text(text);
+        literal
+    }
+
+    pub fn add_text(&mut self, text: &str) -> &mut Self {
+        self.contents.push_str(&escape_string(text));
+        self
+    }
+
+    pub fn add_interpolation(&mut self, expression: &str) -> &mut Self {
+        write!(self.contents, r"\({expression})").expect("Unable to concat string");
+        self
+    }
+}
+
+impl Display for StringLiteral {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, r#""{}""#, self.contents)
+    }
+}
+
+/// Quotes `text` as a Swift string literal, `say "hi"` becomes `"say \"hi\""`
+pub fn string_literal(text: &str) -> String {
+    StringLiteral::new(text).to_string()
+}
+
+/// Escapes `text` so it can be placed between the quotes of a Swift string literal
+pub fn escape_string(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str(r"\\"),
+            '"' => escaped.push_str(r#"\""#),
+            '\n' => escaped.push_str(r"\n"),
+            '\r' => escaped.push_str(r"\r"),
+            '\t' => escaped.push_str(r"\t"),
+            '\0' => escaped.push_str(r"\0"),
+            c if c.is_control() => {
+                write!(escaped, r"\u{{{:x}}}", c as u32).expect("Unable to concat string")
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
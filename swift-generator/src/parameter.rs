@@ -2,6 +2,8 @@ use std::io::Write;
 
 use anyhow::Result;
 
+use crate::{escape_identifier, escape_label};
+
 pub struct ParameterBuilder {
     pub label: Option<String>,
     pub name: String,
@@ -11,9 +13,9 @@ pub struct ParameterBuilder {
 impl ParameterBuilder {
     pub fn generate(&self, writer: &mut impl Write) -> Result<()> {
         if let Some(label) = &self.label {
-            write!(writer, "{label} ")?;
+            write!(writer, "{} ", escape_label(label))?;
         }
-        write!(writer, "{}: ", self.name)?;
+        write!(writer, "{}: ", escape_identifier(&self.name))?;
         write!(writer, "{}", self.parameter_type)?;
         Ok(())
     }
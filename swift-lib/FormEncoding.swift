@@ -2,7 +2,16 @@ public class FormEncoder {
 	public func encode<T: Encodable>(_ value: T) throws -> Data {
 		let formEncoding = FormEncoding()
 		try value.encode(to: formEncoding)
-		return formEncoding.data.result.data(using: .utf8)
+		return Data(formEncoding.data.result.utf8)
+	}
+}
+
+/// Flattens an `Encodable` value into query items, nested values are written as `address[city]` and arrays as `tags[0]`
+public class QueryEncoder {
+	public func encode<T: Encodable>(_ value: T) throws -> [URLQueryItem] {
+		let formEncoding = FormEncoding()
+		try value.encode(to: formEncoding)
+		return formEncoding.data.items.map { URLQueryItem(name: $0.name, value: $0.value) }
 	}
 }
 
@@ -11,14 +20,24 @@ fileprivate class FormEncoding: Encoder {
 	public var userInfo: [CodingUserInfoKey: Any] = [:]
 
 	fileprivate final class Data {
-		private(set) var result = ""
+		private(set) var items: [(name: String, value: String)] = []
+
+		var result: String {
+			items.map { "\($0.name)=\($0.value.addingPercentEncoding(withAllowedCharacters: .alphanumerics)!)" }
+				.joined(separator: "&")
+		}
 
 		func encode(key codingKey: [CodingKey], value: String) {
-			let key = codingKey.map { $0.stringValue }.joined(separator: "")
-			if !result.isEmpty {
-				result += "&"
+			var key = ""
+			for (index, component) in codingKey.enumerated() {
+				// Indexes are already written as `[0]`
+				if index == 0 || component.intValue != nil {
+					key += component.stringValue
+				} else {
+					key += "[\(component.stringValue)]"
+				}
 			}
-			result += "\(key)=\(value.addingPercentEncoding(withAllowedCharacters: .alphanumerics)!)"
+			items.append((name: key, value: value))
 		}
 	}
 
@@ -30,6 +49,7 @@ fileprivate class FormEncoding: Encoder {
 
 	public func container<Key: CodingKey>(keyedBy type: Key.Type) -> KeyedEncodingContainer<Key> {
 		let container = FormEncodingContainer<Key>(to: data)
+		container.codingPath = codingPath
 		return KeyedEncodingContainer(container)
 	}
 
@@ -96,8 +116,8 @@ fileprivate class FormEncodingContainer<Key: CodingKey>: KeyedEncodingContainerP
 	}
 
 	func encode<T: Encodable>(_ value: T, forKey key: Key) throws {
-		let formEncoding = FormEncoding()
-		formEncoding.codingPath.append(key)
+		let formEncoding = FormEncoding(to: data)
+		formEncoding.codingPath = codingPath + [key]
 		try value.encode(to: formEncoding)
 	}
 
@@ -126,13 +146,13 @@ fileprivate class FormEncodingContainer<Key: CodingKey>: KeyedEncodingContainerP
 		forKey key: Key
 	) -> KeyedEncodingContainer<NestedKey> {
 		let container = FormEncodingContainer<NestedKey>(to: data)
-		container.codingPath += [key]
+		container.codingPath = codingPath + [key]
 		return KeyedEncodingContainer(container)
 	}
 
 	func nestedUnkeyedContainer(forKey key: Key) -> UnkeyedEncodingContainer {
 		var container = FormUnkeyedContainer(to: data)
-		container.codingPath += [key]
+		container.codingPath = codingPath + [key]
 		return container
 	}
 
@@ -143,7 +163,7 @@ fileprivate class FormEncodingContainer<Key: CodingKey>: KeyedEncodingContainerP
 
 	func superEncoder(forKey key: Key) -> Encoder {
 		let formEncoding = FormEncoding(to: data)
-		formEncoding.codingPath += [key]
+		formEncoding.codingPath = codingPath + [key]
 		return formEncoding
 	}
 }
@@ -232,7 +252,7 @@ fileprivate struct FormUnkeyedContainer: UnkeyedEncodingContainer {
 
 	mutating func encode<T: Encodable>(_ value: T) throws {
 		let formEncoding = FormEncoding(to: data)
-		formEncoding.codingPath.append(nextIndexedKey())
+		formEncoding.codingPath = codingPath + [nextIndexedKey()]
 		try value.encode(to: formEncoding)			
 	}
 
@@ -240,21 +260,21 @@ fileprivate struct FormUnkeyedContainer: UnkeyedEncodingContainer {
 		keyedBy keyType: NestedKey.Type
 	) -> KeyedEncodingContainer<NestedKey> {
 		let container = FormEncodingContainer<NestedKey>(to: data)
-		container.codingPath += [nextIndexedKey()]
+		container.codingPath = codingPath + [nextIndexedKey()]
 		return KeyedEncodingContainer(container)
 	}
 
 	mutating func nestedUnkeyedContainer() -> UnkeyedEncodingContainer {
-        var container = FormUnkeyedContainer(to: data)
-        container.codingPath += [nextIndexedKey()]
-        return container
-    }
+		var container = FormUnkeyedContainer(to: data)
+		container.codingPath = codingPath + [nextIndexedKey()]
+		return container
+	}
 
 	mutating func superEncoder() -> Encoder {
-        let formEncoding = FormEncoding(to: data)
-        formEncoding.codingPath.append(nextIndexedKey())
-        return formEncoding
-    }
+		let formEncoding = FormEncoding(to: data)
+		formEncoding.codingPath = codingPath + [nextIndexedKey()]
+		return formEncoding
+	}
 }
 
 fileprivate struct FormSingleValueContainer: SingleValueEncodingContainer {
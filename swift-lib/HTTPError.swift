@@ -0,0 +1,52 @@
+import Foundation
+
+/// Thrown by generated services when the server responds with an unexpected status code
+struct HTTPError: Error, CustomStringConvertible {
+    /// Status code of the response, 0 if the response was not an HTTP response
+    let statusCode: Int
+    let headers: [String: String]
+    /// Raw response body, possibly empty
+    let body: Data
+
+    init(statusCode: Int, headers: [String: String], body: Data) {
+        self.statusCode = statusCode
+        self.headers = headers
+        self.body = body
+    }
+
+    init(response: URLResponse, body: Data) {
+        let response = response as? HTTPURLResponse
+        self.init(statusCode: response?.statusCode ?? 0, headers: headerFields(of: response), body: body)
+    }
+
+    var description: String {
+        "HTTPError(statusCode: \(statusCode), body: \(String(decoding: body, as: UTF8.self)))"
+    }
+}
+
+/// Thrown instead of `HTTPError` when the body of the response could be decoded into the error type declared with
+/// an `// Error: ApiError` line
+struct TypedHTTPError<Body: Decodable>: Error {
+    let statusCode: Int
+    let headers: [String: String]
+    let body: Body
+
+    init(statusCode: Int, headers: [String: String], body: Body) {
+        self.statusCode = statusCode
+        self.headers = headers
+        self.body = body
+    }
+
+    init(response: URLResponse, body: Body) {
+        let response = response as? HTTPURLResponse
+        self.init(statusCode: response?.statusCode ?? 0, headers: headerFields(of: response), body: body)
+    }
+}
+
+fileprivate func headerFields(of response: HTTPURLResponse?) -> [String: String] {
+    var headers: [String: String] = [:]
+    for (name, value) in response?.allHeaderFields ?? [:] {
+        headers["\(name)"] = "\(value)"
+    }
+    return headers
+}
@@ -16,6 +16,41 @@ struct MultipartFile: Encodable {
     }
 }
 
+/// Multipart body assembled part by part, for calls declaring `// Part: name={parameter}` lines
+struct MultipartBody {
+    private let boundary: String
+    private var data = Data()
+
+    init(boundary: String) {
+        self.boundary = boundary
+    }
+
+    mutating func add(name: String, value: String) {
+        data.append("--\(boundary)\r\nContent-Disposition: form-data; name=\"\(name)\"\r\n\r\n".data(using: .utf8)!)
+        data.append(value.data(using: .utf8)!)
+        data.append("\r\n".data(using: .utf8)!)
+    }
+
+    mutating func add(name: String, file: MultipartFile) {
+        data.append("--\(boundary)\r\nContent-Disposition: form-data; name=\"\(name)\"; filename=\"\(file.name)\"\r\nContent-Type: \(file.contentType)\r\n\r\n".data(using: .utf8)!)
+        data.append(file.data)
+        data.append("\r\n".data(using: .utf8)!)
+    }
+
+    /// Adds an already encoded JSON value
+    mutating func add(name: String, json: Data) {
+        data.append("--\(boundary)\r\nContent-Disposition: form-data; name=\"\(name)\"\r\nContent-Type: application/json\r\n\r\n".data(using: .utf8)!)
+        data.append(json)
+        data.append("\r\n".data(using: .utf8)!)
+    }
+
+    func build() -> Data {
+        var body = data
+        body.append("--\(boundary)--".data(using: .utf8)!)
+        return body
+    }
+}
+
 public class MultipartEncoder {
     let boundary: String
 
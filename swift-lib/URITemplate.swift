@@ -0,0 +1,111 @@
+import Foundation
+
+/// Expands expressions of RFC 6570 URI templates, such as `{/segments*}` or `{?q,limit}`, the values are already
+/// converted to strings by the generated code
+enum URITemplate {
+    enum Value {
+        case string(String)
+        case list([String])
+        /// Entries of a dictionary, in the order they are expanded
+        case pairs([(String, String)])
+    }
+
+    struct Variable {
+        let name: String
+        /// Undefined values, such as `nil` optionals, are left out of the expansion
+        let value: Value?
+        let explode: Bool
+        let prefix: Int?
+
+        init(_ name: String, _ value: Value?, explode: Bool = false, prefix: Int? = nil) {
+            self.name = name
+            self.value = value
+            self.explode = explode
+            self.prefix = prefix
+        }
+    }
+
+    static func expand(_ op: String, _ variables: [Variable]) -> String {
+        let style = Style(op)
+        var expanded: [String] = []
+        for variable in variables {
+            switch variable.value {
+            case .none:
+                continue
+            case .string(let value):
+                let value = variable.prefix.map { String(value.prefix($0)) } ?? value
+                expanded.append(style.named ? style.pair(variable.name, value) : style.encode(value))
+            case .list(let values) where !values.isEmpty:
+                if !variable.explode {
+                    let joined = values.map(style.encode).joined(separator: ",")
+                    expanded.append(style.named ? "\(variable.name)=\(joined)" : joined)
+                } else if style.named {
+                    expanded.append(contentsOf: values.map { style.pair(variable.name, $0) })
+                } else {
+                    expanded.append(contentsOf: values.map(style.encode))
+                }
+            case .pairs(let pairs) where !pairs.isEmpty:
+                if variable.explode {
+                    expanded.append(contentsOf: pairs.map { key, value in
+                        style.named ? style.pair(style.encode(key), value) : "\(style.encode(key))=\(style.encode(value))"
+                    })
+                } else {
+                    let joined = pairs.flatMap { [style.encode($0.0), style.encode($0.1)] }.joined(separator: ",")
+                    expanded.append(style.named ? "\(variable.name)=\(joined)" : joined)
+                }
+            case .list, .pairs:
+                // Empty lists and dictionaries are undefined as well
+                continue
+            }
+        }
+        if expanded.isEmpty {
+            return ""
+        }
+        return style.first + expanded.joined(separator: style.separator)
+    }
+
+    /// How the values of an operator are written, as listed in appendix A of the RFC
+    private struct Style {
+        let first: String
+        let separator: String
+        /// Values are written as `name=value`
+        let named: Bool
+        /// Written after the name when the value is empty
+        let ifEmpty: String
+        let allowed: CharacterSet
+
+        init(_ op: String) {
+            first = op == "+" ? "" : op
+            switch op {
+            case ".", "/", ";":
+                separator = op
+            case "?", "&":
+                separator = "&"
+            default:
+                separator = ","
+            }
+            named = [";", "?", "&"].contains(op)
+            ifEmpty = op == "?" || op == "&" ? "=" : ""
+            allowed = op == "+" || op == "#" ? .uriTemplateReserved : .uriTemplateUnreserved
+        }
+
+        func encode(_ value: String) -> String {
+            value.addingPercentEncoding(withAllowedCharacters: allowed)!
+        }
+
+        func pair(_ name: String, _ value: String) -> String {
+            value.isEmpty ? name + ifEmpty : "\(name)=\(encode(value))"
+        }
+    }
+}
+
+extension CharacterSet {
+    /// Characters that are never encoded in URI templates
+    static let uriTemplateUnreserved = CharacterSet(
+        charactersIn: "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-._~"
+    )
+    /// Characters kept by reserved expansions, `{+path}` and `{#fragment}`
+    static let uriTemplateReserved = uriTemplateUnreserved.union(CharacterSet(charactersIn: ":/?#[]@!$&'()*+,;="))
+    /// Characters kept by exploded simple expressions, `{path*}`, which only keep slashes in addition to unreserved ones
+    static let uriTemplatePath = uriTemplateUnreserved.union(CharacterSet(charactersIn: "/"))
+}
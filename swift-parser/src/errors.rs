@@ -1,21 +1,32 @@
 use thiserror::Error;
 
-use crate::Token;
+use crate::{Span, Token};
 
 #[derive(Error, Debug)]
-pub(crate) enum ParsingError {
-    #[error("Feature not supported: {0}")]
-    FeatureNotSupported(String),
+pub enum ParsingError {
     #[error("Unexpected character: {0}")]
-    UnexpectedCharacter(char),
+    UnexpectedCharacter(char, Span),
     #[error("Unexpected token: {0:?}")]
-    UnexpectedToken(Token),
+    UnexpectedToken(Token, Span),
     #[error("Unknown identifier: {0}")]
-    UnexpectedIdentifier(String),
+    UnexpectedIdentifier(String, Span),
     #[error("Unexpected state: {0}")]
-    UnexpectedState(String),
+    UnexpectedState(String, Span),
     #[error("Parsing did not complete correctly: {0}")]
-    GeneralError(String),
+    GeneralError(String, Span),
     #[error("Unexpected end of file")]
-    EndOfFile,
+    EndOfFile(Span),
+}
+
+impl ParsingError {
+    pub fn span(&self) -> &Span {
+        match self {
+            ParsingError::UnexpectedCharacter(_, span)
+            | ParsingError::UnexpectedToken(_, span)
+            | ParsingError::UnexpectedIdentifier(_, span)
+            | ParsingError::UnexpectedState(_, span)
+            | ParsingError::GeneralError(_, span)
+            | ParsingError::EndOfFile(span) => span,
+        }
+    }
 }
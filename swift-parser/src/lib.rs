@@ -1,33 +1,83 @@
 use std::io::Read;
 
 use anyhow::Result;
-use parsing::parse;
+use parsing::{parse, TokenIter};
 use tokenizing::tokenize;
+use types::read_type;
 
 mod errors;
+mod models;
 mod parsing;
+mod span;
 #[cfg(test)]
 mod test;
 mod tokenizing;
+mod types;
 
+pub use errors::ParsingError;
+
+pub use models::Model;
+pub use models::ModelKind;
+pub use models::Property;
+pub use parsing::AccessModifier;
 pub use parsing::Definition;
 pub use parsing::Parameter;
 pub use parsing::PostfixModifier;
+pub use span::Span;
+pub use types::TupleElement;
+pub use types::TypeComponent;
+pub use types::TypeRef;
 
 pub fn read_definitions(reader: impl Read) -> Result<Vec<Definition>> {
-    let tokens = tokenize(reader)?;
+    let tokens = tokenize(reader, None)?;
+    parse(tokens)
+}
+
+/// Reads definitions, tagging every span with the given file name
+pub fn read_named_definitions(file_name: &str, reader: impl Read) -> Result<Vec<Definition>> {
+    let tokens = tokenize(reader, Some(file_name.into()))?;
     parse(tokens)
 }
 
+/// Reads a single type, such as `[String: User]`, with spans starting at `span`
+pub fn read_type_ref(source: &str, span: &Span) -> Result<TypeRef> {
+    let tokens = tokenize(source.as_bytes(), span.file.clone())?
+        .into_iter()
+        .map(|(token, token_span)| (token, span.offset(token_span.column - 1, token_span.length)))
+        .collect();
+    let mut tokens = TokenIter::new(tokens);
+    let type_ref = read_type(&mut tokens)?;
+    match tokens.next() {
+        Some((token, span)) => Err(ParsingError::UnexpectedToken(token, span).into()),
+        None => Ok(type_ref),
+    }
+}
+
 #[derive(PartialEq, Debug)]
-enum Token {
+pub enum Token {
     LineComment(String),
-    Identifier(String),
+    BlockComment(String),
+    DocComment(String),
+    /// Name, and whether it was escaped with backticks, making keywords such as `` `any` `` plain names
+    Identifier(String, bool),
+    /// Contents of a string literal without its delimiters, escapes are kept as written
+    StringLiteral(String),
+    Number(String),
     LeftBrace,
     RightBrace,
     LeftParenthesis,
     RightParenthesis,
+    LeftBracket,
+    RightBracket,
+    LeftAngle,
+    RightAngle,
     Operator(String),
     Colon,
     Comma,
+    Dot,
+    QuestionMark,
+    Semicolon,
+    At,
+    Hash,
+    Backslash,
 }
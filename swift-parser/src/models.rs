@@ -0,0 +1,181 @@
+use anyhow::Result;
+
+use crate::{
+    errors::ParsingError,
+    parsing::{skip_block, skip_declaration, skip_parenthesized, AccessModifier, TokenIter},
+    types::{read_type, TypeRef},
+    Span, Token,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ModelKind {
+    Struct,
+    Class,
+    Enum,
+    /// Extensions only contribute conformances to the extended type
+    Extension,
+}
+
+/// A `struct`, `class`, `enum` or `extension` declaration
+#[derive(Clone, Debug)]
+pub struct Model {
+    pub kind: ModelKind,
+    pub name: String,
+    pub access: AccessModifier,
+    /// Inherited types in declaration order, for enums the first one can be the raw type
+    pub conformances: Vec<TypeRef>,
+    /// Stored instance properties, computed and static ones are left out
+    pub properties: Vec<Property>,
+    pub cases: Vec<String>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug)]
+pub struct Property {
+    pub name: String,
+    /// Declared type, or the type of a literal initial value, `None` when it can't be known
+    pub property_type: Option<TypeRef>,
+    pub span: Span,
+}
+
+impl Model {
+    pub fn conforms_to(&self, name: &str) -> bool {
+        self.conformances.iter().any(|c| c.name() == Some(name))
+    }
+}
+
+/// Skips generic parameters, `<T: Codable>`
+pub(crate) fn skip_generic_parameters(tokens: &mut TokenIter) -> Result<()> {
+    if !matches!(tokens.peek(), Some(Token::LeftAngle)) {
+        return Ok(());
+    }
+
+    let mut depth = 0;
+    loop {
+        match tokens.next() {
+            Some((Token::LeftAngle, _)) => depth += 1,
+            Some((Token::RightAngle, _)) => depth -= 1,
+            Some(_) => (),
+            None => return Err(tokens.end_of_file().into()),
+        }
+        if depth == 0 {
+            return Ok(());
+        }
+    }
+}
+
+/// Reads the inheritance clause, `: Codable, Equatable`, if there is one
+pub(crate) fn read_conformances(tokens: &mut TokenIter) -> Result<Vec<TypeRef>> {
+    let mut conformances = vec![];
+    if !matches!(tokens.peek(), Some(Token::Colon)) {
+        return Ok(conformances);
+    }
+    tokens.next();
+
+    loop {
+        conformances.push(read_type(tokens)?);
+        match tokens.peek() {
+            Some(Token::Comma) => {
+                tokens.next();
+            }
+            _ => return Ok(conformances),
+        }
+    }
+}
+
+/// Reads the names of `case a, b(Int), c = "c"`
+pub(crate) fn read_cases(tokens: &mut TokenIter) -> Result<Vec<String>> {
+    let mut cases = vec![];
+    loop {
+        match tokens.next() {
+            Some((Token::Identifier(name, _), _)) => cases.push(name),
+            Some((token, span)) => return Err(ParsingError::UnexpectedToken(token, span).into()),
+            None => return Err(tokens.end_of_file().into()),
+        }
+
+        if let Some(Token::LeftParenthesis) = tokens.peek() {
+            skip_parenthesized(tokens)?;
+        }
+        if let Some(Token::Operator(operator)) = tokens.peek() {
+            if operator == "=" {
+                tokens.next();
+                // Raw values are literals, possibly negative numbers
+                if let Some(Token::Operator(_)) = tokens.peek() {
+                    tokens.next();
+                }
+                tokens.next();
+            }
+        }
+
+        match tokens.peek() {
+            Some(Token::Comma) => {
+                tokens.next();
+            }
+            _ => return Ok(cases),
+        }
+    }
+}
+
+/// Reads a property after `let` or `var`, returns `None` if it doesn't store a value
+pub(crate) fn read_property(tokens: &mut TokenIter) -> Result<Option<Property>> {
+    let (name, span) = match tokens.next() {
+        Some((Token::Identifier(name, _), span)) => (name, span),
+        // Patterns like `let (a, b) = pair`
+        Some(_) => {
+            skip_declaration(tokens);
+            return Ok(None);
+        }
+        None => return Err(tokens.end_of_file().into()),
+    };
+
+    let mut property_type = None;
+    if let Some(Token::Colon) = tokens.peek() {
+        tokens.next();
+        property_type = Some(read_type(tokens)?);
+    }
+
+    match tokens.peek() {
+        Some(Token::Operator(operator)) if operator == "=" => {
+            tokens.next();
+            if property_type.is_none() {
+                property_type = tokens.peek().and_then(literal_type);
+            }
+            skip_declaration(tokens);
+        }
+        Some(Token::LeftBrace) => {
+            tokens.next();
+            // Observers keep the property stored, any other accessor makes it computed
+            let observed = matches!(
+                tokens.peek(),
+                Some(Token::Identifier(accessor, _)) if accessor == "willSet" || accessor == "didSet"
+            );
+            skip_block(tokens)?;
+            if !observed {
+                return Ok(None);
+            }
+        }
+        _ => (),
+    }
+
+    Ok(Some(Property {
+        name,
+        property_type,
+        span,
+    }))
+}
+
+/// Type of a value starting with a literal, `var count = 0`
+fn literal_type(token: &Token) -> Option<TypeRef> {
+    match token {
+        Token::StringLiteral(_) => Some(TypeRef::named("String")),
+        Token::Number(number) => {
+            let is_hex = number.starts_with("0x");
+            let is_decimal = number.contains('.') || (!is_hex && number.contains(['e', 'E']));
+            Some(TypeRef::named(if is_decimal { "Double" } else { "Int" }))
+        }
+        Token::Identifier(value, _) if value == "true" || value == "false" => {
+            Some(TypeRef::named("Bool"))
+        }
+        _ => None,
+    }
+}
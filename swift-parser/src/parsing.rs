@@ -1,19 +1,73 @@
 use std::{iter::Peekable, mem, vec::IntoIter};
 
-use crate::{errors::ParsingError, Token};
+use crate::{
+    errors::ParsingError,
+    models::{
+        read_cases, read_conformances, read_property, skip_generic_parameters, Model, ModelKind,
+    },
+    types::{read_named, read_type, TypeRef},
+    Span, Token,
+};
 
 use anyhow::Result;
 
-pub(crate) fn parse(token: Vec<Token>) -> Result<Vec<Definition>> {
+pub(crate) fn parse(token: Vec<(Token, Span)>) -> Result<Vec<Definition>> {
     Parser::new().parse(token)
 }
 
-type TokenIter = Peekable<IntoIter<Token>>;
+/// Token iterator that remembers the last span, so running out of tokens can be reported
+pub(crate) struct TokenIter {
+    tokens: Peekable<IntoIter<(Token, Span)>>,
+    last: Span,
+}
+
+impl TokenIter {
+    pub(crate) fn new(tokens: Vec<(Token, Span)>) -> Self {
+        TokenIter {
+            tokens: tokens.into_iter().peekable(),
+            last: Span::default(),
+        }
+    }
+
+    pub(crate) fn next(&mut self) -> Option<(Token, Span)> {
+        let next = self.tokens.next();
+        if let Some((_, span)) = &next {
+            self.last = span.clone();
+        }
+        next
+    }
+
+    pub(crate) fn peek(&mut self) -> Option<&Token> {
+        self.tokens.peek().map(|(token, _)| token)
+    }
+
+    /// Line of the next token, if there is one
+    fn peek_line(&mut self) -> Option<usize> {
+        self.tokens.peek().map(|(_, span)| span.line)
+    }
+
+    fn last_line(&self) -> usize {
+        self.last.line
+    }
+
+    /// Span right after the last consumed token
+    pub(crate) fn end_span(&self) -> Span {
+        self.last.offset(self.last.length, 1)
+    }
+
+    pub(crate) fn end_of_file(&self) -> ParsingError {
+        ParsingError::EndOfFile(self.end_span())
+    }
+}
 
 struct Parser {
     state: State,
     definitions: Vec<Definition>,
     previous_states: Vec<(State, Vec<Definition>)>,
+    documentation: Vec<String>,
+    access: Option<AccessModifier>,
+    /// Set by `static` and `class` modifiers, such members are not stored properties
+    static_member: bool,
 }
 
 impl Parser {
@@ -22,103 +76,248 @@ impl Parser {
             state: State::None,
             definitions: vec![],
             previous_states: vec![],
+            documentation: vec![],
+            access: None,
+            static_member: false,
         }
     }
 
-    fn parse(mut self, tokens: Vec<Token>) -> Result<Vec<Definition>> {
-        let mut tokens = tokens.into_iter().peekable();
+    fn parse(mut self, tokens: Vec<(Token, Span)>) -> Result<Vec<Definition>> {
+        let mut tokens = TokenIter::new(tokens);
 
-        while let Some(token) = tokens.next() {
+        loop {
+            let previous_line = tokens.last_line();
+            let (token, span) = match tokens.next() {
+                Some(next) => next,
+                None => break,
+            };
             match token {
-                Token::Identifier(value) => self.handle_identifier(&value, &mut tokens)?,
-                Token::LineComment(comment) => self.definitions.push(Definition::Comment(comment)),
-                Token::RightBrace => self.pop_state()?,
-                value => return Err(ParsingError::UnexpectedToken(value).into()),
+                Token::Identifier(value, _) => self.handle_identifier(&value, span, &mut tokens)?,
+                // Trailing comments belong to the code before them
+                Token::LineComment(_) if span.line == previous_line => (),
+                Token::LineComment(comment) => {
+                    self.definitions.push(Definition::Comment(comment, span))
+                }
+                Token::BlockComment(_) => (),
+                Token::DocComment(comment) => self
+                    .documentation
+                    .extend(comment.split('\n').map(|line| line.to_owned())),
+                Token::RightBrace => self.pop_state(span)?,
+                Token::At => skip_attribute(&mut tokens)?,
+                // Compiler directives such as `#if os(iOS)`, the code they wrap is parsed as usual
+                Token::Hash => self.skip_unknown_declaration(&mut tokens),
+                Token::Semicolon => (),
+                value => return Err(ParsingError::UnexpectedToken(value, span).into()),
             }
         }
 
         if !self.previous_states.is_empty() {
-            return Err(ParsingError::GeneralError("Did not handle all states".into()).into());
+            return Err(ParsingError::GeneralError(
+                "Did not handle all states".into(),
+                tokens.end_span(),
+            )
+            .into());
         }
         if !matches!(self.state, State::None) {
-            return Err(ParsingError::GeneralError(format!(
-                "Did not finalize state: {:?}",
-                self.state
-            ))
+            return Err(ParsingError::GeneralError(
+                format!("Did not finalize state: {:?}", self.state),
+                tokens.end_span(),
+            )
             .into());
         }
 
         Ok(self.definitions)
     }
 
-    fn handle_identifier(&mut self, identifier: &str, tokens: &mut TokenIter) -> Result<()> {
+    fn handle_identifier(
+        &mut self,
+        identifier: &str,
+        span: Span,
+        tokens: &mut TokenIter,
+    ) -> Result<()> {
         match identifier {
-            "protocol" => self.handle_protocol(tokens)?,
-            "func" => {
+            "import" => {
+                let import = self.handle_import(span, tokens)?;
+                self.definitions.push(import);
+            }
+            "protocol" => {
+                self.documentation.clear();
+                self.handle_protocol(tokens)?
+            }
+            "func" if matches!(self.state, State::ProtocolWithName(..)) => {
+                self.access = None;
+                self.static_member = false;
                 let function = self.handle_function(tokens)?;
                 self.definitions.push(function);
             }
-            value => todo!("Identifiers: {value:?}"),
+            "struct" => self.handle_model(ModelKind::Struct, tokens)?,
+            "enum" => self.handle_model(ModelKind::Enum, tokens)?,
+            "extension" => self.handle_model(ModelKind::Extension, tokens)?,
+            // `class func` and `class var` use `class` as a modifier
+            "class" if !matches!(tokens.peek(), Some(Token::Identifier(next, _)) if is_keyword(next)) => {
+                self.handle_model(ModelKind::Class, tokens)?
+            }
+            "class" | "static" => self.static_member = true,
+            "let" | "var" if matches!(self.state, State::Model(_)) => {
+                self.handle_property(tokens)?
+            }
+            "case" if matches!(&self.state, State::Model(model) if model.kind == ModelKind::Enum) =>
+            {
+                let cases = read_cases(tokens)?;
+                if let State::Model(model) = &mut self.state {
+                    model.cases.extend(cases);
+                }
+            }
+            value => {
+                if let Some(access) = AccessModifier::from_keyword(value) {
+                    self.access = Some(access);
+                    skip_modifier_detail(tokens)?;
+                } else if !DECLARATION_MODIFIERS.contains(&value) {
+                    self.skip_unknown_declaration(tokens);
+                }
+            }
         }
         Ok(())
     }
 
-    fn handle_protocol(&mut self, tokens: &mut TokenIter) -> Result<()> {
-        let name = match tokens.next() {
-            Some(Token::Identifier(name)) => name,
-            Some(token) => return Err(ParsingError::UnexpectedToken(token).into()),
-            None => return Err(ParsingError::EndOfFile.into()),
+    fn handle_import(&mut self, span: Span, tokens: &mut TokenIter) -> Result<Definition> {
+        self.clear_pending();
+
+        // `import struct Foundation.Data` imports a single symbol
+        if let Some(Token::Identifier(kind, _)) = tokens.peek() {
+            if IMPORT_KINDS.contains(&kind.as_str()) {
+                tokens.next();
+            }
+        }
+
+        let mut path = String::new();
+        loop {
+            match tokens.next() {
+                Some((Token::Identifier(name, _), _)) => path.push_str(&name),
+                Some((token, span)) => {
+                    return Err(ParsingError::UnexpectedToken(token, span).into())
+                }
+                None => return Err(tokens.end_of_file().into()),
+            }
+            match tokens.peek() {
+                Some(Token::Dot) => {
+                    tokens.next();
+                    path.push('.');
+                }
+                _ => break,
+            }
+        }
+        Ok(Definition::Import(path, span))
+    }
+
+    fn handle_model(&mut self, kind: ModelKind, tokens: &mut TokenIter) -> Result<()> {
+        let access = self.access.take().unwrap_or_default();
+        self.clear_pending();
+
+        let (name, span) = match tokens.next() {
+            // Extended types can be qualified, `extension Foundation.Data`
+            Some((Token::Identifier(name, _), span)) if kind == ModelKind::Extension => {
+                let extended = read_named(name, tokens)?;
+                (extended.name().unwrap_or_default().to_owned(), span)
+            }
+            Some((Token::Identifier(name, _), span)) => (name, span),
+            Some((token, span)) => return Err(ParsingError::UnexpectedToken(token, span).into()),
+            None => return Err(tokens.end_of_file().into()),
         };
+        skip_generic_parameters(tokens)?;
+        let conformances = read_conformances(tokens)?;
+        skip_to_body(tokens)?;
+
+        self.previous_states
+            .push((mem::take(&mut self.state), mem::take(&mut self.definitions)));
+        self.state = State::Model(Box::new(Model {
+            kind,
+            name,
+            access,
+            conformances,
+            properties: vec![],
+            cases: vec![],
+            span,
+        }));
+        Ok(())
+    }
 
-        match tokens.next() {
-            Some(Token::LeftBrace) => {
-                self.previous_states
-                    .push((mem::take(&mut self.state), mem::take(&mut self.definitions)));
-                self.state = State::ProtocolWithName(name);
+    fn handle_property(&mut self, tokens: &mut TokenIter) -> Result<()> {
+        let is_static = self.static_member;
+        self.clear_pending();
+
+        let property = read_property(tokens)?;
+        if let (Some(property), State::Model(model)) = (property, &mut self.state) {
+            // Extensions can't add stored properties
+            if !is_static && model.kind != ModelKind::Extension {
+                model.properties.push(property);
             }
-            Some(token) => return Err(ParsingError::UnexpectedToken(token).into()),
-            None => return Err(ParsingError::EndOfFile.into()),
         }
         Ok(())
     }
 
+    fn handle_protocol(&mut self, tokens: &mut TokenIter) -> Result<()> {
+        let access = self.access.take().unwrap_or_default();
+        let (name, span) = match tokens.next() {
+            Some((Token::Identifier(name, _), span)) => (name, span),
+            Some((token, span)) => return Err(ParsingError::UnexpectedToken(token, span).into()),
+            None => return Err(tokens.end_of_file().into()),
+        };
+
+        // Inherited protocols and `where` clauses don't affect the generated code
+        skip_to_body(tokens)?;
+        self.previous_states
+            .push((mem::take(&mut self.state), mem::take(&mut self.definitions)));
+        self.state = State::ProtocolWithName(name, access, span);
+        Ok(())
+    }
+
+    /// Skips a declaration the parser doesn't understand, such as an `actor` or a `func` body
+    fn skip_unknown_declaration(&mut self, tokens: &mut TokenIter) {
+        self.clear_pending();
+        skip_declaration(tokens);
+    }
+
+    /// Forgets documentation and modifiers that were meant for the current declaration
+    fn clear_pending(&mut self) {
+        self.documentation.clear();
+        self.access = None;
+        self.static_member = false;
+    }
+
     fn handle_function(&mut self, tokens: &mut TokenIter) -> Result<Definition> {
-        let name = match tokens.next() {
-            Some(Token::Identifier(name)) => name,
-            Some(token) => return Err(ParsingError::UnexpectedToken(token).into()),
-            None => return Err(ParsingError::EndOfFile.into()),
+        let (name, span) = match tokens.next() {
+            Some((Token::Identifier(name, _), span)) => (name, span),
+            Some((token, span)) => return Err(ParsingError::UnexpectedToken(token, span).into()),
+            None => return Err(tokens.end_of_file().into()),
         };
 
         match tokens.next() {
-            Some(Token::LeftParenthesis) => (),
-            Some(token) => return Err(ParsingError::UnexpectedToken(token).into()),
-            None => return Err(ParsingError::EndOfFile.into()),
+            Some((Token::LeftParenthesis, _)) => (),
+            Some((token, span)) => return Err(ParsingError::UnexpectedToken(token, span).into()),
+            None => return Err(tokens.end_of_file().into()),
         };
 
         let parameters = read_parameters(tokens)?;
 
         match tokens.next() {
-            Some(Token::RightParenthesis) => (),
-            Some(token) => return Err(ParsingError::UnexpectedToken(token).into()),
-            None => return Err(ParsingError::EndOfFile.into()),
+            Some((Token::RightParenthesis, _)) => (),
+            Some((token, span)) => return Err(ParsingError::UnexpectedToken(token, span).into()),
+            None => return Err(tokens.end_of_file().into()),
         };
 
         let modifiers = Self::parse_modifiers(tokens)?;
 
-        let return_type: Option<String>;
-        match tokens.peek() {
+        let return_type = match tokens.peek() {
             Some(Token::Operator(value)) => {
-                if value != "->" {
-                    return Err(ParsingError::UnexpectedIdentifier("->".into()).into());
-                }
-                tokens.next();
-                if let Some(Token::Identifier(name)) = tokens.next() {
-                    return_type = Some(name)
-                } else {
-                    return Err(ParsingError::GeneralError("Missing return type".into()).into());
+                let is_arrow = value == "->";
+                let (_, span) = tokens.next().unwrap();
+                if !is_arrow {
+                    return Err(ParsingError::UnexpectedIdentifier("->".into(), span).into());
                 }
+                Some(read_type(tokens)?)
             }
-            _ => return_type = None,
+            _ => None,
         };
 
         Ok(Definition::Function {
@@ -126,56 +325,197 @@ impl Parser {
             parameters,
             modifiers,
             return_type,
+            documentation: mem::take(&mut self.documentation),
+            span,
         })
     }
 
     fn parse_modifiers(tokens: &mut TokenIter) -> Result<Vec<PostfixModifier>> {
         let mut modifiers = vec![];
 
-        while let Some(Token::Identifier(modifier)) = tokens.peek() {
+        while let Some(Token::Identifier(modifier, _)) = tokens.peek() {
             if modifier == "async" {
+                let (_, span) = tokens.next().unwrap();
                 if modifiers.contains(&PostfixModifier::Throws) {
                     return Err(ParsingError::GeneralError(
                         "async cannot come after throws".into(),
+                        span,
                     )
                     .into());
                 }
                 if modifiers.contains(&PostfixModifier::Async) {
-                    return Err(
-                        ParsingError::GeneralError("async cannot come after async".into()).into(),
-                    );
+                    return Err(ParsingError::GeneralError(
+                        "async cannot come after async".into(),
+                        span,
+                    )
+                    .into());
                 }
                 modifiers.push(PostfixModifier::Async);
-                tokens.next();
             } else if modifier == "throws" {
+                let (_, span) = tokens.next().unwrap();
                 if modifiers.contains(&PostfixModifier::Throws) {
                     return Err(ParsingError::GeneralError(
                         "throws cannot come after throws".into(),
+                        span,
                     )
                     .into());
                 }
                 modifiers.push(PostfixModifier::Throws);
-                tokens.next();
+            } else {
+                break;
             }
         }
         Ok(modifiers)
     }
 
-    fn pop_state(&mut self) -> Result<()> {
+    fn pop_state(&mut self, span: Span) -> Result<()> {
         let definitions = mem::take(&mut self.definitions);
         let (mut state, previous_definitions) = match self.previous_states.pop() {
             Some((state, definitions)) => (state, definitions),
-            None => return Err(ParsingError::GeneralError("Illegal internal state".into()).into()),
+            None => {
+                return Err(
+                    ParsingError::GeneralError("Illegal internal state".into(), span).into(),
+                )
+            }
         };
         mem::swap(&mut self.state, &mut state);
         self.definitions = previous_definitions;
         match state {
-            State::ProtocolWithName(name) => {
-                self.definitions
-                    .push(Definition::Protocol(name, definitions));
+            State::ProtocolWithName(name, access, span) => {
+                self.definitions.push(Definition::Protocol {
+                    name,
+                    access,
+                    definitions,
+                    span,
+                });
                 Ok(())
             }
-            state => Err(ParsingError::UnexpectedState(format!("{state:?}")).into()),
+            State::Model(model) => {
+                self.definitions.push(Definition::Model(*model));
+                // Nested models are hoisted, other members are not needed
+                self.definitions.extend(
+                    definitions
+                        .into_iter()
+                        .filter(|d| matches!(d, Definition::Model(_))),
+                );
+                Ok(())
+            }
+            state => Err(ParsingError::UnexpectedState(format!("{state:?}"), span).into()),
+        }
+    }
+}
+
+/// Skips the rest of a declaration.
+/// It ends with its braced body, a `;`, or when the next declaration starts on a new line.
+pub(crate) fn skip_declaration(tokens: &mut TokenIter) {
+    let mut depth = 0usize;
+    loop {
+        let starts_line = tokens.peek_line() > Some(tokens.last_line());
+        match tokens.peek() {
+            None => break,
+            Some(next) if depth == 0 && ends_declaration(next, starts_line) => break,
+            _ => (),
+        }
+
+        match tokens.next() {
+            Some((Token::LeftBrace | Token::LeftParenthesis | Token::LeftBracket, _)) => depth += 1,
+            Some((Token::RightParenthesis | Token::RightBracket, _)) => {
+                depth = depth.saturating_sub(1)
+            }
+            Some((Token::RightBrace, _)) => {
+                depth = depth.saturating_sub(1);
+                // Closures can be followed by more of the expression, `{ ... }()`
+                let continues = matches!(
+                    tokens.peek(),
+                    Some(Token::Dot | Token::LeftParenthesis | Token::QuestionMark)
+                );
+                if depth == 0 && !continues {
+                    break;
+                }
+            }
+            Some((Token::Semicolon, _)) if depth == 0 => break,
+            _ => (),
+        }
+    }
+}
+
+/// Skips the rest of a braced block whose `{` was already consumed
+pub(crate) fn skip_block(tokens: &mut TokenIter) -> Result<()> {
+    let mut depth = 1;
+    while depth > 0 {
+        match tokens.next() {
+            Some((Token::LeftBrace, _)) => depth += 1,
+            Some((Token::RightBrace, _)) => depth -= 1,
+            Some(_) => (),
+            None => return Err(tokens.end_of_file().into()),
+        }
+    }
+    Ok(())
+}
+
+/// Skips inheritance and `where` clauses up to and including the opening brace of the body
+fn skip_to_body(tokens: &mut TokenIter) -> Result<()> {
+    loop {
+        match tokens.next() {
+            Some((Token::LeftBrace, _)) => return Ok(()),
+            Some(_) => (),
+            None => return Err(tokens.end_of_file().into()),
+        }
+    }
+}
+
+fn is_keyword(word: &str) -> bool {
+    DECLARATION_KEYWORDS.contains(&word)
+        || DECLARATION_MODIFIERS.contains(&word)
+        || AccessModifier::from_keyword(word).is_some()
+}
+
+/// Whether `token` can't be a part of the declaration being skipped at its top level
+fn ends_declaration(token: &Token, starts_line: bool) -> bool {
+    match token {
+        Token::RightBrace => true,
+        Token::Identifier(keyword, _) => starts_line && is_keyword(keyword),
+        Token::At
+        | Token::Hash
+        | Token::LineComment(_)
+        | Token::BlockComment(_)
+        | Token::DocComment(_) => starts_line,
+        _ => false,
+    }
+}
+
+/// Skips an attribute such as `@MainActor` or `@available(iOS 15, *)`
+fn skip_attribute(tokens: &mut TokenIter) -> Result<()> {
+    match tokens.next() {
+        Some((Token::Identifier(_, _), _)) => (),
+        Some((token, span)) => return Err(ParsingError::UnexpectedToken(token, span).into()),
+        None => return Err(tokens.end_of_file().into()),
+    }
+    if let Some(Token::LeftParenthesis) = tokens.peek() {
+        skip_parenthesized(tokens)?;
+    }
+    Ok(())
+}
+
+/// Skips the `(set)` of modifiers like `private(set)`
+fn skip_modifier_detail(tokens: &mut TokenIter) -> Result<()> {
+    if let Some(Token::LeftParenthesis) = tokens.peek() {
+        skip_parenthesized(tokens)?;
+    }
+    Ok(())
+}
+
+pub(crate) fn skip_parenthesized(tokens: &mut TokenIter) -> Result<()> {
+    let mut depth = 0;
+    loop {
+        match tokens.next() {
+            Some((Token::LeftParenthesis, _)) => depth += 1,
+            Some((Token::RightParenthesis, _)) => depth -= 1,
+            Some(_) => (),
+            None => return Err(tokens.end_of_file().into()),
+        }
+        if depth == 0 {
+            return Ok(());
         }
     }
 }
@@ -187,46 +527,51 @@ fn read_parameters(tokens: &mut TokenIter) -> Result<Vec<Parameter>> {
         let peeked = tokens.peek();
         match peeked {
             Some(Token::RightParenthesis) => break,
-            Some(Token::Identifier(_)) => (),
-            Some(_) => return Err(ParsingError::UnexpectedToken(tokens.next().unwrap()).into()),
-            None => return Err(ParsingError::EndOfFile.into()),
+            Some(Token::Identifier(_, _)) => (),
+            Some(_) => {
+                let (token, span) = tokens.next().unwrap();
+                return Err(ParsingError::UnexpectedToken(token, span).into());
+            }
+            None => return Err(tokens.end_of_file().into()),
         }
 
-        let label = match tokens.next() {
-            Some(Token::Identifier(name)) => name,
-            Some(token) => return Err(ParsingError::UnexpectedToken(token).into()),
-            None => return Err(ParsingError::EndOfFile.into()),
+        let (label, label_span) = match tokens.next() {
+            Some((Token::Identifier(name, _), span)) => (name, span),
+            Some((token, span)) => return Err(ParsingError::UnexpectedToken(token, span).into()),
+            None => return Err(tokens.end_of_file().into()),
         };
 
-        let label_and_name: (Option<String>, String);
+        let label_and_name: (Option<String>, String, Span);
         let peeked = tokens.peek();
         match peeked {
-            Some(Token::Identifier(value)) => {
-                label_and_name = (Some(label), value.into());
-                tokens.next();
+            Some(Token::Identifier(value, _)) => {
+                let value = value.clone();
+                let (_, span) = tokens.next().unwrap();
+                label_and_name = (Some(label), value, span);
                 match tokens.next() {
-                    Some(Token::Colon) => (),
-                    Some(token) => return Err(ParsingError::UnexpectedToken(token).into()),
-                    None => return Err(ParsingError::EndOfFile.into()),
+                    Some((Token::Colon, _)) => (),
+                    Some((token, span)) => {
+                        return Err(ParsingError::UnexpectedToken(token, span).into())
+                    }
+                    None => return Err(tokens.end_of_file().into()),
                 }
             }
             Some(Token::Colon) => {
-                label_and_name = (None, label);
+                label_and_name = (None, label, label_span);
                 tokens.next();
             }
-            Some(_) => return Err(ParsingError::UnexpectedToken(tokens.next().unwrap()).into()),
-            None => return Err(ParsingError::EndOfFile.into()),
+            Some(_) => {
+                let (token, span) = tokens.next().unwrap();
+                return Err(ParsingError::UnexpectedToken(token, span).into());
+            }
+            None => return Err(tokens.end_of_file().into()),
         }
 
-        let type_name = match tokens.next() {
-            Some(Token::Identifier(name)) => name,
-            Some(token) => return Err(ParsingError::UnexpectedToken(token).into()),
-            None => return Err(ParsingError::EndOfFile.into()),
-        };
         parameters.push(Parameter {
             label: label_and_name.0,
             name: label_and_name.1,
-            parameter_type: type_name,
+            parameter_type: read_type(tokens)?,
+            span: label_and_name.2,
         });
 
         if let Some(next) = tokens.peek() {
@@ -246,20 +591,41 @@ fn read_parameters(tokens: &mut TokenIter) -> Result<Vec<Parameter>> {
 pub struct Parameter {
     pub label: Option<String>,
     pub name: String,
-    pub parameter_type: String,
+    pub parameter_type: TypeRef,
+    pub span: Span,
 }
 
 #[derive(Debug)]
 pub enum Definition {
-    Comment(String),
+    Comment(String, Span),
     Function {
         name: String,
         parameters: Vec<Parameter>,
         modifiers: Vec<PostfixModifier>,
-        return_type: Option<String>,
+        return_type: Option<TypeRef>,
+        documentation: Vec<String>,
+        span: Span,
+    },
+    Import(String, Span),
+    Model(Model),
+    Protocol {
+        name: String,
+        access: AccessModifier,
+        definitions: Vec<Definition>,
+        span: Span,
     },
-    Parameter(Option<String>, String, String),
-    Protocol(String, Vec<Definition>),
+}
+
+impl Definition {
+    pub fn span(&self) -> &Span {
+        match self {
+            Definition::Comment(_, span)
+            | Definition::Import(_, span)
+            | Definition::Model(Model { span, .. })
+            | Definition::Function { span, .. }
+            | Definition::Protocol { span, .. } => span,
+        }
+    }
 }
 
 #[derive(PartialEq, Debug)]
@@ -268,9 +634,90 @@ pub enum PostfixModifier {
     Throws,
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum AccessModifier {
+    Open,
+    Public,
+    Package,
+    #[default]
+    Internal,
+    FilePrivate,
+    Private,
+}
+
+impl AccessModifier {
+    fn from_keyword(keyword: &str) -> Option<AccessModifier> {
+        match keyword {
+            "open" => Some(AccessModifier::Open),
+            "public" => Some(AccessModifier::Public),
+            "package" => Some(AccessModifier::Package),
+            "internal" => Some(AccessModifier::Internal),
+            "fileprivate" => Some(AccessModifier::FilePrivate),
+            "private" => Some(AccessModifier::Private),
+            _ => None,
+        }
+    }
+}
+
+/// Keywords that start a declaration, skipping stops at them when they start a line
+const DECLARATION_KEYWORDS: [&str; 19] = [
+    "actor",
+    "associatedtype",
+    "case",
+    "class",
+    "deinit",
+    "enum",
+    "extension",
+    "func",
+    "import",
+    "init",
+    "let",
+    "macro",
+    "operator",
+    "precedencegroup",
+    "protocol",
+    "struct",
+    "subscript",
+    "typealias",
+    "var",
+];
+
+/// Modifiers other than access levels, these don't change how the declaration is read
+const DECLARATION_MODIFIERS: [&str; 17] = [
+    "convenience",
+    "dynamic",
+    "final",
+    "indirect",
+    "infix",
+    "lazy",
+    "mutating",
+    "nonisolated",
+    "nonmutating",
+    "optional",
+    "override",
+    "postfix",
+    "prefix",
+    "required",
+    "static",
+    "unowned",
+    "weak",
+];
+
+const IMPORT_KINDS: [&str; 8] = [
+    "class",
+    "enum",
+    "func",
+    "let",
+    "protocol",
+    "struct",
+    "typealias",
+    "var",
+];
+
 #[derive(Debug, Default)]
 enum State {
     #[default]
     None,
-    ProtocolWithName(String),
+    ProtocolWithName(String, AccessModifier, Span),
+    Model(Box<Model>),
 }
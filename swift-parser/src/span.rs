@@ -0,0 +1,64 @@
+use std::{
+    fmt::{self, Display, Formatter, Write},
+    sync::Arc,
+};
+
+/// Location of a token or definition in the source, lines and columns start at 1
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Span {
+    pub file: Option<Arc<str>>,
+    pub line: usize,
+    pub column: usize,
+    pub length: usize,
+}
+
+impl Span {
+    /// Span on the same line, starting `columns` characters after this one
+    pub fn offset(&self, columns: usize, length: usize) -> Span {
+        Span {
+            file: self.file.clone(),
+            line: self.line,
+            column: self.column + columns,
+            length,
+        }
+    }
+
+    /// Formats the message rustc-style, quoting the offending line and marking the span with carets
+    pub fn render(&self, message: &str, source: &str) -> String {
+        let mut output = format!("error: {message}\n");
+        let line_number = self.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+        writeln!(output, "{gutter}--> {self}").expect("Unable to write to string");
+
+        let line = match self.line.checked_sub(1).and_then(|l| source.lines().nth(l)) {
+            Some(line) => line,
+            None => return output,
+        };
+        let prefix: String = line.chars().take(self.column.saturating_sub(1)).collect();
+        let marker = format!(
+            "{}{}",
+            " ".repeat(expand_tabs(&prefix).chars().count()),
+            "^".repeat(self.length.max(1))
+        );
+        write!(
+            output,
+            "{gutter} |\n{line_number} | {}\n{gutter} | {marker}\n",
+            expand_tabs(line)
+        )
+        .expect("Unable to write to string");
+        output
+    }
+}
+
+impl Display for Span {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{file}:")?;
+        }
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+fn expand_tabs(line: &str) -> String {
+    line.replace('\t', "    ")
+}
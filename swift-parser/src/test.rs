@@ -1,31 +1,56 @@
-use std::fs::File;
+use std::{fs::File, io::Read};
 
 use anyhow::Result;
 
 use crate::{
-    parsing::{parse, Definition, PostfixModifier},
+    errors::ParsingError,
+    models::ModelKind,
+    parsing::{parse, AccessModifier, Definition, PostfixModifier},
+    read_definitions, read_named_definitions, read_type_ref,
     tokenizing::tokenize,
-    Token,
+    Span, Token, TypeComponent, TypeRef,
 };
 
+fn tokens(input: impl Read) -> Result<Vec<Token>> {
+    Ok(tokenize(input, None)?
+        .into_iter()
+        .map(|(token, _)| token)
+        .collect())
+}
+
+/// Places every token on its own line
+fn spanned(tokens: Vec<Token>) -> Vec<(Token, Span)> {
+    tokens
+        .into_iter()
+        .enumerate()
+        .map(|(index, token)| {
+            let span = Span {
+                line: index + 1,
+                ..Span::default()
+            };
+            (token, span)
+        })
+        .collect()
+}
+
 #[test]
 fn tokenize_simple() -> Result<()> {
     let input = File::open("../samples/Simple.swift")?;
 
-    let tokens = tokenize(input)?;
+    let tokens = tokens(input)?;
 
     assert_eq!(
         vec![
-            Token::Identifier("protocol".to_owned()),
-            Token::Identifier("Simple".to_owned()),
+            Token::Identifier("protocol".to_owned(), false),
+            Token::Identifier("Simple".to_owned(), false),
             Token::LeftBrace,
             Token::LineComment("GET /get".to_owned()),
-            Token::Identifier("func".to_owned()),
-            Token::Identifier("get".to_owned()),
+            Token::Identifier("func".to_owned(), false),
+            Token::Identifier("get".to_owned(), false),
             Token::LeftParenthesis,
             Token::RightParenthesis,
-            Token::Identifier("async".to_owned()),
-            Token::Identifier("throws".to_owned()),
+            Token::Identifier("async".to_owned(), false),
+            Token::Identifier("throws".to_owned(), false),
             Token::RightBrace,
         ],
         tokens
@@ -38,22 +63,22 @@ fn tokenize_simple() -> Result<()> {
 fn tokenize_returning() -> Result<()> {
     let input = File::open("../samples/Return.swift")?;
 
-    let tokens = tokenize(input)?;
+    let tokens = tokens(input)?;
 
     assert_eq!(
         vec![
-            Token::Identifier("protocol".to_owned()),
-            Token::Identifier("Return".to_owned()),
+            Token::Identifier("protocol".to_owned(), false),
+            Token::Identifier("Return".to_owned(), false),
             Token::LeftBrace,
             Token::LineComment("GET /get".to_owned()),
-            Token::Identifier("func".to_owned()),
-            Token::Identifier("get".to_owned()),
+            Token::Identifier("func".to_owned(), false),
+            Token::Identifier("get".to_owned(), false),
             Token::LeftParenthesis,
             Token::RightParenthesis,
-            Token::Identifier("async".to_owned()),
-            Token::Identifier("throws".to_owned()),
+            Token::Identifier("async".to_owned(), false),
+            Token::Identifier("throws".to_owned(), false),
             Token::Operator("->".to_owned()),
-            Token::Identifier("Hello".to_owned()),
+            Token::Identifier("Hello".to_owned(), false),
             Token::RightBrace,
         ],
         tokens,
@@ -66,34 +91,75 @@ fn tokenize_returning() -> Result<()> {
 fn tokenize_params() -> Result<()> {
     let input = File::open("../samples/QueryParameter.swift")?;
 
-    let tokens = tokenize(input)?;
+    let tokens = tokens(input)?;
 
     assert_eq!(
         vec![
-            Token::Identifier("protocol".to_owned()),
-            Token::Identifier("QueryParameter".to_owned()),
+            Token::Identifier("protocol".to_owned(), false),
+            Token::Identifier("QueryParameter".to_owned(), false),
             Token::LeftBrace,
             Token::LineComment("GET /get?q=:query".to_owned()),
-            Token::Identifier("func".to_owned()),
-            Token::Identifier("get".to_owned()),
+            Token::Identifier("func".to_owned(), false),
+            Token::Identifier("get".to_owned(), false),
             Token::LeftParenthesis,
-            Token::Identifier("query".to_owned()),
+            Token::Identifier("query".to_owned(), false),
             Token::Colon,
-            Token::Identifier("String".to_owned()),
+            Token::Identifier("String".to_owned(), false),
             Token::RightParenthesis,
-            Token::Identifier("async".to_owned()),
-            Token::Identifier("throws".to_owned()),
+            Token::Identifier("async".to_owned(), false),
+            Token::Identifier("throws".to_owned(), false),
             Token::LineComment("GET /get?q=:query&q2=something".to_owned()),
-            Token::Identifier("func".to_owned()),
-            Token::Identifier("get".to_owned()),
+            Token::Identifier("func".to_owned(), false),
+            Token::Identifier("get".to_owned(), false),
             Token::LeftParenthesis,
-            Token::Identifier("for".to_owned()),
-            Token::Identifier("query".to_owned()),
+            Token::Identifier("for".to_owned(), false),
+            Token::Identifier("query".to_owned(), false),
             Token::Colon,
-            Token::Identifier("String".to_owned()),
+            Token::Identifier("String".to_owned(), false),
+            Token::RightParenthesis,
+            Token::Identifier("async".to_owned(), false),
+            Token::Identifier("throws".to_owned(), false),
+            Token::RightBrace,
+        ],
+        tokens
+    );
+
+    Ok(())
+}
+
+#[test]
+fn tokenize_comments() -> Result<()> {
+    let input = File::open("../samples/Documented.swift")?;
+
+    let tokens = tokens(input)?;
+
+    assert_eq!(
+        vec![
+            Token::BlockComment(
+                "* Licensed under the Apache License, Version 2.0\n * /* nested block comments are allowed */"
+                    .to_owned()
+            ),
+            Token::Identifier("protocol".to_owned(), false),
+            Token::Identifier("Documented".to_owned(), false),
+            Token::LeftBrace,
+            Token::DocComment("Fetches the greeting.".to_owned()),
+            Token::DocComment("".to_owned()),
+            Token::DocComment("Returns nothing.".to_owned()),
+            Token::LineComment("GET /get".to_owned()),
+            Token::Identifier("func".to_owned(), false),
+            Token::Identifier("get".to_owned(), false),
+            Token::LeftParenthesis,
             Token::RightParenthesis,
-            Token::Identifier("async".to_owned()),
-            Token::Identifier("throws".to_owned()),
+            Token::Identifier("async".to_owned(), false),
+            Token::Identifier("throws".to_owned(), false),
+            Token::DocComment("Posts a greeting.".to_owned()),
+            Token::LineComment("POST /post".to_owned()),
+            Token::Identifier("func".to_owned(), false),
+            Token::Identifier("post".to_owned(), false),
+            Token::LeftParenthesis,
+            Token::RightParenthesis,
+            Token::Identifier("async".to_owned(), false),
+            Token::Identifier("throws".to_owned(), false),
             Token::RightBrace,
         ],
         tokens
@@ -105,23 +171,26 @@ fn tokenize_params() -> Result<()> {
 #[test]
 fn parse_simple() -> Result<()> {
     let tokens = vec![
-        Token::Identifier("protocol".to_owned()),
-        Token::Identifier("Simple".to_owned()),
+        Token::Identifier("protocol".to_owned(), false),
+        Token::Identifier("Simple".to_owned(), false),
         Token::LeftBrace,
         Token::LineComment("GET /get".to_owned()),
-        Token::Identifier("func".to_owned()),
-        Token::Identifier("get".to_owned()),
+        Token::Identifier("func".to_owned(), false),
+        Token::Identifier("get".to_owned(), false),
         Token::LeftParenthesis,
         Token::RightParenthesis,
         Token::RightBrace,
     ];
 
-    let definitions = parse(tokens)?;
+    let definitions = parse(spanned(tokens))?;
     assert_eq!(1, definitions.len());
-    if let Definition::Protocol(name, definitions) = &definitions[0] {
+    if let Definition::Protocol {
+        name, definitions, ..
+    } = &definitions[0]
+    {
         assert_eq!("Simple", name);
         assert_eq!(2, definitions.len());
-        if let Definition::Comment(comment) = &definitions[0] {
+        if let Definition::Comment(comment, _) = &definitions[0] {
             assert_eq!("GET /get", comment);
         } else {
             panic!("Expected comment");
@@ -141,25 +210,28 @@ fn parse_simple() -> Result<()> {
 #[test]
 fn parse_async_throws() -> Result<()> {
     let tokens = vec![
-        Token::Identifier("protocol".to_owned()),
-        Token::Identifier("Simple".to_owned()),
+        Token::Identifier("protocol".to_owned(), false),
+        Token::Identifier("Simple".to_owned(), false),
         Token::LeftBrace,
         Token::LineComment("GET /get".to_owned()),
-        Token::Identifier("func".to_owned()),
-        Token::Identifier("get".to_owned()),
+        Token::Identifier("func".to_owned(), false),
+        Token::Identifier("get".to_owned(), false),
         Token::LeftParenthesis,
         Token::RightParenthesis,
-        Token::Identifier("async".to_owned()),
-        Token::Identifier("throws".to_owned()),
+        Token::Identifier("async".to_owned(), false),
+        Token::Identifier("throws".to_owned(), false),
         Token::RightBrace,
     ];
 
-    let definitions = parse(tokens)?;
+    let definitions = parse(spanned(tokens))?;
     assert_eq!(1, definitions.len());
-    if let Definition::Protocol(name, definitions) = &definitions[0] {
+    if let Definition::Protocol {
+        name, definitions, ..
+    } = &definitions[0]
+    {
         assert_eq!("Simple", name);
         assert_eq!(2, definitions.len());
-        if let Definition::Comment(comment) = &definitions[0] {
+        if let Definition::Comment(comment, _) = &definitions[0] {
             assert_eq!("GET /get", comment);
         } else {
             panic!("Expected comment");
@@ -169,13 +241,14 @@ fn parse_async_throws() -> Result<()> {
             parameters,
             modifiers,
             return_type,
+            ..
         } = &definitions[1]
         {
             assert_eq!("get", name);
             assert!(parameters.is_empty());
             assert!(modifiers.contains(&PostfixModifier::Async));
             assert!(modifiers.contains(&PostfixModifier::Throws));
-            assert!(matches!(return_type, None));
+            assert!(return_type.is_none());
         } else {
             panic!("Expected function");
         }
@@ -189,27 +262,30 @@ fn parse_async_throws() -> Result<()> {
 #[test]
 fn parse_returns() -> Result<()> {
     let tokens = vec![
-        Token::Identifier("protocol".to_owned()),
-        Token::Identifier("Return".to_owned()),
+        Token::Identifier("protocol".to_owned(), false),
+        Token::Identifier("Return".to_owned(), false),
         Token::LeftBrace,
         Token::LineComment("GET /get".to_owned()),
-        Token::Identifier("func".to_owned()),
-        Token::Identifier("get".to_owned()),
+        Token::Identifier("func".to_owned(), false),
+        Token::Identifier("get".to_owned(), false),
         Token::LeftParenthesis,
         Token::RightParenthesis,
-        Token::Identifier("async".to_owned()),
-        Token::Identifier("throws".to_owned()),
+        Token::Identifier("async".to_owned(), false),
+        Token::Identifier("throws".to_owned(), false),
         Token::Operator("->".to_owned()),
-        Token::Identifier("Hello".to_owned()),
+        Token::Identifier("Hello".to_owned(), false),
         Token::RightBrace,
     ];
 
-    let definitions = parse(tokens)?;
+    let definitions = parse(spanned(tokens))?;
     assert_eq!(1, definitions.len());
-    if let Definition::Protocol(name, definitions) = &definitions[0] {
+    if let Definition::Protocol {
+        name, definitions, ..
+    } = &definitions[0]
+    {
         assert_eq!("Return", name);
         assert_eq!(2, definitions.len());
-        if let Definition::Comment(comment) = &definitions[0] {
+        if let Definition::Comment(comment, _) = &definitions[0] {
             assert_eq!("GET /get", comment);
         } else {
             panic!("Expected comment");
@@ -219,6 +295,7 @@ fn parse_returns() -> Result<()> {
             parameters,
             modifiers,
             return_type,
+            ..
         } = &definitions[1]
         {
             assert_eq!("get", name);
@@ -226,7 +303,7 @@ fn parse_returns() -> Result<()> {
             assert!(modifiers.contains(&PostfixModifier::Async));
             assert!(modifiers.contains(&PostfixModifier::Throws));
             if let Some(value) = return_type {
-                assert_eq!("Hello", value);
+                assert_eq!(&TypeRef::named("Hello"), value);
             } else {
                 panic!("Wanted return type");
             }
@@ -243,23 +320,23 @@ fn parse_returns() -> Result<()> {
 #[test]
 fn parse_parameter_list() -> Result<()> {
     let tokens = vec![
-        Token::Identifier("protocol".to_owned()),
-        Token::Identifier("Simple".to_owned()),
+        Token::Identifier("protocol".to_owned(), false),
+        Token::Identifier("Simple".to_owned(), false),
         Token::LeftBrace,
         Token::LineComment("GET /get".to_owned()),
-        Token::Identifier("func".to_owned()),
-        Token::Identifier("get".to_owned()),
+        Token::Identifier("func".to_owned(), false),
+        Token::Identifier("get".to_owned(), false),
         Token::LeftParenthesis,
-        Token::Identifier("query".to_owned()),
+        Token::Identifier("query".to_owned(), false),
         Token::Colon,
-        Token::Identifier("String".to_owned()),
+        Token::Identifier("String".to_owned(), false),
         Token::RightParenthesis,
         Token::RightBrace,
     ];
 
-    let definitions = parse(tokens)?;
+    let definitions = parse(spanned(tokens))?;
     assert_eq!(1, definitions.len());
-    if let Definition::Protocol(_, definitions) = &definitions[0] {
+    if let Definition::Protocol { definitions, .. } = &definitions[0] {
         if let Definition::Function {
             name, parameters, ..
         } = &definitions[1]
@@ -267,9 +344,58 @@ fn parse_parameter_list() -> Result<()> {
             assert_eq!("get", name);
             assert_eq!(1, parameters.len());
             let parameter = &parameters[0];
-            assert!(matches!(parameter.label, None));
+            assert!(parameter.label.is_none());
             assert_eq!("query".to_owned(), parameter.name);
-            assert_eq!("String".to_owned(), parameter.parameter_type);
+            assert_eq!(TypeRef::named("String"), parameter.parameter_type);
+        } else {
+            panic!("Expected function");
+        }
+    } else {
+        panic!("Invalid parsed structure")
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_documentation() -> Result<()> {
+    let tokens = vec![
+        Token::BlockComment("License".to_owned()),
+        Token::Identifier("protocol".to_owned(), false),
+        Token::Identifier("Simple".to_owned(), false),
+        Token::LeftBrace,
+        Token::DocComment("First line".to_owned()),
+        Token::DocComment("Second line\nThird line".to_owned()),
+        Token::LineComment("GET /get".to_owned()),
+        Token::Identifier("func".to_owned(), false),
+        Token::Identifier("get".to_owned(), false),
+        Token::LeftParenthesis,
+        Token::RightParenthesis,
+        Token::LineComment("GET /other".to_owned()),
+        Token::Identifier("func".to_owned(), false),
+        Token::Identifier("other".to_owned(), false),
+        Token::LeftParenthesis,
+        Token::RightParenthesis,
+        Token::RightBrace,
+    ];
+
+    let definitions = parse(spanned(tokens))?;
+    assert_eq!(1, definitions.len());
+    if let Definition::Protocol { definitions, .. } = &definitions[0] {
+        assert_eq!(4, definitions.len());
+        if let Definition::Function { documentation, .. } = &definitions[1] {
+            assert_eq!(
+                vec![
+                    "First line".to_owned(),
+                    "Second line".to_owned(),
+                    "Third line".to_owned()
+                ],
+                *documentation
+            );
+        } else {
+            panic!("Expected function");
+        }
+        if let Definition::Function { documentation, .. } = &definitions[3] {
+            assert!(documentation.is_empty());
         } else {
             panic!("Expected function");
         }
@@ -279,22 +405,438 @@ fn parse_parameter_list() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn parse_types() -> Result<()> {
+    let input = File::open("../samples/Types.swift")?;
+
+    let definitions = parse(tokenize(input, None)?)?;
+    let definitions = match &definitions[0] {
+        Definition::Protocol { definitions, .. } => definitions,
+        _ => panic!("Invalid parsed structure"),
+    };
+    let return_types: Vec<_> = definitions
+        .iter()
+        .filter_map(|d| match d {
+            Definition::Function { return_type, .. } => Some(return_type.clone()),
+            _ => None,
+        })
+        .collect();
+
+    let user = || Box::new(TypeRef::named("User"));
+    assert_eq!(
+        vec![
+            Some(TypeRef::Array(user())),
+            Some(TypeRef::Dictionary(
+                Box::new(TypeRef::named("String")),
+                user()
+            )),
+            Some(TypeRef::Optional(user())),
+            Some(TypeRef::Named(vec![TypeComponent {
+                name: "Page".to_owned(),
+                generics: vec![TypeRef::named("User")],
+            }])),
+            Some(TypeRef::Dictionary(
+                Box::new(TypeRef::named("String")),
+                Box::new(TypeRef::Array(user()))
+            )),
+            None,
+        ],
+        return_types
+    );
+
+    if let Definition::Function { parameters, .. } = &definitions[9] {
+        assert_eq!(
+            TypeRef::Array(Box::new(TypeRef::Named(vec![
+                TypeComponent {
+                    name: "Foundation".to_owned(),
+                    generics: vec![],
+                },
+                TypeComponent {
+                    name: "Data".to_owned(),
+                    generics: vec![],
+                },
+            ]))),
+            parameters[0].parameter_type
+        );
+    } else {
+        panic!("Expected function");
+    }
+    if let Definition::Function { parameters, .. } = &definitions[11] {
+        assert_eq!(
+            TypeRef::Opaque(Box::new(TypeRef::named("Encodable"))),
+            parameters[0].parameter_type
+        );
+    } else {
+        panic!("Expected function");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn parse_tuple_and_existential_types() -> Result<()> {
+    let tokens = vec![
+        Token::Identifier("protocol".to_owned(), false),
+        Token::Identifier("Tuples".to_owned(), false),
+        Token::LeftBrace,
+        Token::Identifier("func".to_owned(), false),
+        Token::Identifier("get".to_owned(), false),
+        Token::LeftParenthesis,
+        Token::Identifier("error".to_owned(), false),
+        Token::Colon,
+        Token::LeftParenthesis,
+        Token::Identifier("any".to_owned(), false),
+        Token::Identifier("Error".to_owned(), false),
+        Token::RightParenthesis,
+        Token::QuestionMark,
+        Token::RightParenthesis,
+        Token::Operator("->".to_owned()),
+        Token::LeftParenthesis,
+        Token::Identifier("code".to_owned(), false),
+        Token::Colon,
+        Token::Identifier("Int".to_owned(), false),
+        Token::Comma,
+        Token::Identifier("String".to_owned(), false),
+        Token::RightParenthesis,
+        Token::RightBrace,
+    ];
+
+    let definitions = match parse(spanned(tokens))?.pop() {
+        Some(Definition::Protocol { definitions, .. }) => definitions,
+        _ => panic!("Expected protocol"),
+    };
+    if let Definition::Function {
+        parameters,
+        return_type,
+        ..
+    } = &definitions[0]
+    {
+        assert_eq!("(any Error)?", parameters[0].parameter_type.to_string());
+        assert_eq!(
+            Some("(code: Int, String)".to_owned()),
+            return_type.as_ref().map(|t| t.to_string())
+        );
+    } else {
+        panic!("Expected function");
+    }
+    Ok(())
+}
+
 #[test]
 #[should_panic]
 fn parse_async_throws_invalid_order() {
     let tokens = vec![
-        Token::Identifier("protocol".to_owned()),
-        Token::Identifier("Simple".to_owned()),
+        Token::Identifier("protocol".to_owned(), false),
+        Token::Identifier("Simple".to_owned(), false),
         Token::LeftBrace,
         Token::LineComment("GET /get".to_owned()),
-        Token::Identifier("func".to_owned()),
-        Token::Identifier("get".to_owned()),
+        Token::Identifier("func".to_owned(), false),
+        Token::Identifier("get".to_owned(), false),
         Token::LeftParenthesis,
         Token::RightParenthesis,
-        Token::Identifier("throws".to_owned()),
-        Token::Identifier("async".to_owned()),
+        Token::Identifier("throws".to_owned(), false),
+        Token::Identifier("async".to_owned(), false),
         Token::RightBrace,
     ];
 
-    parse(tokens).unwrap();
+    parse(spanned(tokens)).unwrap();
+}
+
+#[test]
+fn tokenize_spans() -> Result<()> {
+    let input = File::open("../samples/QueryParameter.swift")?;
+
+    let tokens = tokenize(input, Some("QueryParameter.swift".into()))?;
+
+    let (token, span) = &tokens[3];
+    assert_eq!(&Token::LineComment("GET /get?q=:query".to_owned()), token);
+    assert_eq!((2, 5, 17), (span.line, span.column, span.length));
+    assert_eq!("QueryParameter.swift:2:5", span.to_string());
+
+    let (token, span) = &tokens[7];
+    assert_eq!(&Token::Identifier("query".to_owned(), false), token);
+    assert_eq!((3, 11, 5), (span.line, span.column, span.length));
+
+    Ok(())
+}
+
+#[test]
+fn parse_error_has_location() -> Result<()> {
+    let source = "protocol Broken {\n\t// GET /get\n\tfunc get(query String)\n}\n";
+
+    let error = read_named_definitions("Broken.swift", source.as_bytes()).unwrap_err();
+    let error: ParsingError = error.downcast()?;
+
+    assert_eq!(
+        "error: Unexpected token: RightParenthesis\n \
+          --> Broken.swift:3:23\n  \
+           |\n\
+         3 |     func get(query String)\n  \
+           |                          ^\n",
+        error.span().render(&error.to_string(), source)
+    );
+    Ok(())
+}
+
+#[test]
+fn read_standalone_type() -> Result<()> {
+    let span = Span {
+        file: None,
+        line: 4,
+        column: 13,
+        length: 17,
+    };
+
+    let type_ref = read_type_ref("[String: ApiError]", &span)?;
+    assert_eq!(
+        TypeRef::Dictionary(
+            Box::new(TypeRef::named("String")),
+            Box::new(TypeRef::named("ApiError"))
+        ),
+        type_ref
+    );
+
+    let error = read_type_ref("ApiError Detail", &span).unwrap_err();
+    let error: ParsingError = error.downcast()?;
+    assert_eq!(
+        (4, 22, 6),
+        (error.span().line, error.span().column, error.span().length)
+    );
+    Ok(())
+}
+
+#[test]
+fn tokenize_literals_and_operators() -> Result<()> {
+    let source = r####"let a = "say \"hi\" \(name("x"))" + #"raw "quoted" \#(b)"# ?? 1.5e-3; _ = 0..<2 != !c
+"####;
+
+    let tokens = tokens(source.as_bytes())?;
+
+    assert_eq!(
+        vec![
+            Token::Identifier("let".to_owned(), false),
+            Token::Identifier("a".to_owned(), false),
+            Token::Operator("=".to_owned()),
+            Token::StringLiteral(r#"say \"hi\" \(name("x"))"#.to_owned()),
+            Token::Operator("+".to_owned()),
+            Token::StringLiteral(r#"raw "quoted" \#(b)"#.to_owned()),
+            Token::QuestionMark,
+            Token::QuestionMark,
+            Token::Number("1.5e-3".to_owned()),
+            Token::Semicolon,
+            Token::Identifier("_".to_owned(), false),
+            Token::Operator("=".to_owned()),
+            Token::Number("0".to_owned()),
+            Token::Dot,
+            Token::Dot,
+            Token::LeftAngle,
+            Token::Number("2".to_owned()),
+            Token::Operator("!=".to_owned()),
+            Token::Operator("!".to_owned()),
+            Token::Identifier("c".to_owned(), false),
+        ],
+        tokens
+    );
+    Ok(())
+}
+
+#[test]
+fn tokenize_multiline_string() -> Result<()> {
+    let source = "let text = \"\"\"\n\tquote: \"\"\n\t\"\"\"\nlet range = 0..<10\n";
+
+    let tokens = tokens(source.as_bytes())?;
+
+    assert_eq!(
+        Token::StringLiteral("\n\tquote: \"\"\n\t".to_owned()),
+        tokens[3]
+    );
+    assert_eq!(
+        vec![
+            Token::Number("0".to_owned()),
+            Token::Dot,
+            Token::Dot,
+            Token::LeftAngle,
+            Token::Number("10".to_owned()),
+        ],
+        tokens[7..]
+    );
+    Ok(())
+}
+
+#[test]
+fn parse_models() -> Result<()> {
+    let input = File::open("../samples/Models.swift")?;
+
+    let definitions = read_definitions(input)?;
+
+    assert_eq!(7, definitions.len());
+    assert!(matches!(&definitions[0], Definition::Import(path, _) if path == "Foundation"));
+    assert!(matches!(&definitions[1], Definition::Import(path, _) if path == "Foundation.UUID"));
+    if let Definition::Model(model) = &definitions[2] {
+        assert_eq!(
+            ("Hello", ModelKind::Struct),
+            (model.name.as_str(), model.kind)
+        );
+        assert!(model.conforms_to("Codable"));
+        let properties: Vec<_> = model
+            .properties
+            .iter()
+            .map(|p| {
+                let property_type = p.property_type.as_ref().map(|t| t.to_string());
+                (p.name.as_str(), property_type)
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                ("id", Some("UUID".to_owned())),
+                ("message", Some("String".to_owned())),
+                ("count", Some("Int".to_owned())),
+            ],
+            properties
+        );
+    } else {
+        panic!("Expected struct");
+    }
+    if let Definition::Model(model) = &definitions[3] {
+        assert_eq!(("Mood", ModelKind::Enum), (model.name.as_str(), model.kind));
+        assert_eq!(vec!["happy", "sad"], model.cases);
+        assert_eq!(Some("String"), model.conformances[0].name());
+    } else {
+        panic!("Expected enum");
+    }
+    if let Definition::Model(model) = &definitions[4] {
+        assert_eq!(
+            ("Hello", ModelKind::Extension),
+            (model.name.as_str(), model.kind)
+        );
+        assert!(model.conforms_to("Equatable"));
+        assert!(model.properties.is_empty());
+    } else {
+        panic!("Expected extension");
+    }
+    if let Definition::Protocol {
+        name,
+        access,
+        definitions,
+        ..
+    } = &definitions[6]
+    {
+        assert_eq!("Models", name);
+        assert_eq!(&AccessModifier::Public, access);
+        assert_eq!(7, definitions.len());
+    } else {
+        panic!("Expected protocol");
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_stored_properties() -> Result<()> {
+    let source = "public final class Account: Base, Codable {
+	static let shared = Account()
+	class var kind: String { \"account\" }
+	var balance: Decimal {
+		didSet { print(balance) }
+	}
+	var total: Decimal {
+		get { balance }
+	}
+	lazy var rate = 1.5
+
+	struct Owner: Codable {
+		let name: String
+	}
+
+	init() {}
+	func reset() { balance = 0 }
+}
+";
+
+    let definitions = read_definitions(source.as_bytes())?;
+
+    assert_eq!(2, definitions.len());
+    if let Definition::Model(model) = &definitions[0] {
+        assert_eq!(
+            ("Account", ModelKind::Class),
+            (model.name.as_str(), model.kind)
+        );
+        assert_eq!(AccessModifier::Public, model.access);
+        let properties: Vec<_> = model.properties.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(vec!["balance", "rate"], properties);
+    } else {
+        panic!("Expected class");
+    }
+    assert!(matches!(&definitions[1], Definition::Model(model) if model.name == "Owner"));
+    Ok(())
+}
+
+#[test]
+fn parse_skips_unknown_protocol_members() -> Result<()> {
+    let source = "protocol Members {
+\tassociatedtype Model: Decodable
+\tvar version: String { get } // not a call definition
+\tstatic var shared: Self { get }
+\tinit(baseUrl: String)
+
+\t// GET /get
+\tmutating func get() async throws -> Model
+}
+";
+
+    let definitions = read_definitions(source.as_bytes())?;
+
+    if let Some(Definition::Protocol { definitions, .. }) = definitions.first() {
+        assert_eq!(2, definitions.len());
+        assert!(
+            matches!(&definitions[0], Definition::Comment(comment, _) if comment == "GET /get")
+        );
+        assert!(matches!(&definitions[1], Definition::Function { name, .. } if name == "get"));
+    } else {
+        panic!("Expected protocol");
+    }
+    Ok(())
+}
+
+#[test]
+fn tokenize_identifiers() -> Result<()> {
+    let source = "func get(_ user_id: String, `default` $value: Int)";
+
+    let tokens = tokens(source.as_bytes())?;
+
+    assert_eq!(
+        vec![
+            Token::Identifier("func".to_owned(), false),
+            Token::Identifier("get".to_owned(), false),
+            Token::LeftParenthesis,
+            Token::Identifier("_".to_owned(), false),
+            Token::Identifier("user_id".to_owned(), false),
+            Token::Colon,
+            Token::Identifier("String".to_owned(), false),
+            Token::Comma,
+            Token::Identifier("default".to_owned(), true),
+            Token::Identifier("$value".to_owned(), false),
+            Token::Colon,
+            Token::Identifier("Int".to_owned(), false),
+            Token::RightParenthesis,
+        ],
+        tokens
+    );
+    Ok(())
+}
+
+#[test]
+fn parse_escaped_keyword_types() -> Result<()> {
+    let span = Span {
+        file: None,
+        line: 1,
+        column: 1,
+        length: 11,
+    };
+
+    assert_eq!(TypeRef::named("any"), read_type_ref("`any`", &span)?);
+    assert_eq!(
+        TypeRef::Existential(Box::new(TypeRef::named("some"))),
+        read_type_ref("any `some`", &span)?
+    );
+    Ok(())
 }
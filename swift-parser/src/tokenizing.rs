@@ -1,45 +1,156 @@
-use std::io::{BufReader, Read};
-use std::iter::Peekable;
+use std::collections::VecDeque;
+use std::io::{self, BufReader, Read};
+use std::sync::Arc;
 
 use anyhow::Result;
 use utf8_chars::{BufReadCharsExt, Chars};
 
 use crate::errors::ParsingError;
+use crate::span::Span;
 use crate::Token;
 
-pub(crate) fn tokenize(reader: impl Read) -> Result<Vec<Token>> {
+pub(crate) fn tokenize(reader: impl Read, file: Option<Arc<str>>) -> Result<Vec<(Token, Span)>> {
     let mut tokens = vec![];
     let mut reader = BufReader::new(reader);
-    let mut chars = reader.chars().peekable();
-
-    while let Some(char) = chars.next() {
-        match char? {
-            c if c.is_alphabetic() => tokens.push(read_identifier(c, &mut chars)?),
-            c if c == '{' => tokens.push(Token::LeftBrace),
-            c if c == '}' => tokens.push(Token::RightBrace),
-            c if c == '(' => tokens.push(Token::LeftParenthesis),
-            c if c == ')' => tokens.push(Token::RightParenthesis),
-            c if c == '/' => tokens.push(read_comment(&mut chars)?),
-            c if c == ':' => tokens.push(Token::Colon),
-            c if c == ',' => tokens.push(Token::Comma),
-            c if c == '-' => tokens.push(read_operator(c, &mut chars)?),
+    let mut chars = SourceChars {
+        chars: reader.chars(),
+        buffer: VecDeque::new(),
+        file,
+        line: 1,
+        column: 1,
+    };
+
+    loop {
+        let start = chars.span();
+        let char = match chars.next() {
+            Some(char) => char?,
+            None => break,
+        };
+        let token = match char {
+            c if is_identifier_head(c) => read_identifier(c, &mut chars)?,
+            // Implicit closure parameters and property wrapper projections, `$0`, `$value`
+            '$' => read_identifier('$', &mut chars)?,
+            '`' => read_escaped_identifier(&mut chars)?,
+            '{' => Token::LeftBrace,
+            '}' => Token::RightBrace,
+            '(' => Token::LeftParenthesis,
+            ')' => Token::RightParenthesis,
+            '[' => Token::LeftBracket,
+            ']' => Token::RightBracket,
+            '<' => Token::LeftAngle,
+            '>' => Token::RightAngle,
+            '/' if matches!(chars.peek(), Some(Ok('/' | '*'))) => {
+                tokens.push(read_comment(&mut chars, start)?);
+                continue;
+            }
+            ':' => Token::Colon,
+            ',' => Token::Comma,
+            '.' => Token::Dot,
+            '?' => Token::QuestionMark,
+            ';' => Token::Semicolon,
+            '@' => Token::At,
+            '\\' => Token::Backslash,
+            '"' => read_string(&mut chars, 0)?,
+            '#' => match chars.peek() {
+                Some(Ok('#' | '"')) => read_raw_string(&mut chars)?,
+                _ => Token::Hash,
+            },
+            c if c.is_ascii_digit() => read_number(c, &mut chars)?,
+            c if OPERATOR_CHARACTERS.contains(c) => read_operator(c, &mut chars)?,
             c if c.is_whitespace() => continue,
-            value => return Err(ParsingError::UnexpectedCharacter(value).into()),
-        }
+            value => return Err(ParsingError::UnexpectedCharacter(value, start).into()),
+        };
+        tokens.push((token, chars.span_from(&start)));
     }
 
     Ok(tokens)
 }
 
-fn read_identifier(
-    prefix: char,
-    iterator: &mut Peekable<Chars<'_, BufReader<impl Read>>>,
-) -> Result<Token> {
+/// Characters that can form operators, `<`, `>`, `?` and `.` are separate tokens since types use them
+const OPERATOR_CHARACTERS: &str = "=-+!*/%&|^~";
+
+/// Character iterator that keeps track of the current line and column
+struct SourceChars<'a, R: Read> {
+    chars: Chars<'a, BufReader<R>>,
+    buffer: VecDeque<io::Result<char>>,
+    file: Option<Arc<str>>,
+    line: usize,
+    column: usize,
+}
+
+impl<R: Read> SourceChars<'_, R> {
+    fn peek(&mut self) -> Option<&io::Result<char>> {
+        self.peek_nth(0)
+    }
+
+    /// Looks `n` characters past the next one without consuming anything
+    fn peek_nth(&mut self, n: usize) -> Option<&io::Result<char>> {
+        while self.buffer.len() <= n {
+            self.buffer.push_back(self.chars.next()?);
+        }
+        self.buffer.get(n)
+    }
+
+    /// Consumes the next character if it is `expected`
+    fn next_if(&mut self, expected: char) -> bool {
+        let matches = matches!(self.peek(), Some(Ok(char)) if *char == expected);
+        if matches {
+            self.next();
+        }
+        matches
+    }
+
+    /// Span of the next character
+    fn span(&self) -> Span {
+        Span {
+            file: self.file.clone(),
+            line: self.line,
+            column: self.column,
+            length: 1,
+        }
+    }
+
+    /// Span from `start` up to the current position, multi-line spans only mark the start
+    fn span_from(&self, start: &Span) -> Span {
+        let length = if start.line == self.line {
+            self.column - start.column
+        } else {
+            1
+        };
+        Span {
+            length,
+            ..start.clone()
+        }
+    }
+}
+
+impl<R: Read> Iterator for SourceChars<'_, R> {
+    type Item = io::Result<char>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.buffer.pop_front().or_else(|| self.chars.next());
+        match next {
+            Some(Ok('\n')) => {
+                self.line += 1;
+                self.column = 1;
+            }
+            Some(Ok(_)) => self.column += 1,
+            _ => (),
+        }
+        next
+    }
+}
+
+fn read_identifier(prefix: char, iterator: &mut SourceChars<'_, impl Read>) -> Result<Token> {
     let mut name = String::new();
     name.push(prefix);
-    while let Some(value) = iterator.peek() {
-        let value = unwrap_char(value)?;
-        if value.is_alphanumeric() {
+    loop {
+        let span = iterator.span();
+        let value = match iterator.peek() {
+            Some(value) => unwrap_char(value, span)?,
+            None => break,
+        };
+        if is_identifier_head(value) || value.is_numeric() {
             name.push(value)
         } else {
             break;
@@ -47,20 +158,64 @@ fn read_identifier(
         iterator.next();
     }
 
-    Ok(Token::Identifier(name))
+    Ok(Token::Identifier(name, false))
+}
+
+fn is_identifier_head(char: char) -> bool {
+    char.is_alphabetic() || char == '_'
+}
+
+/// Reads `` `default` `` as the identifier `default`, keywords are escaped again when generating
+fn read_escaped_identifier(iterator: &mut SourceChars<'_, impl Read>) -> Result<Token> {
+    let mut name = String::new();
+    loop {
+        let span = iterator.span();
+        match iterator.next() {
+            Some(Ok('`')) if !name.is_empty() => return Ok(Token::Identifier(name, true)),
+            Some(Ok(char))
+                if is_identifier_head(char) || (!name.is_empty() && char.is_numeric()) =>
+            {
+                name.push(char)
+            }
+            Some(Ok(char)) => return Err(ParsingError::UnexpectedCharacter(char, span).into()),
+            Some(Err(error)) => return Err(error.into()),
+            None => return Err(ParsingError::EndOfFile(span).into()),
+        }
+    }
 }
 
-fn read_comment(iterator: &mut Peekable<Chars<'_, BufReader<impl Read>>>) -> Result<Token> {
-    let comment_type = iterator.next().ok_or(ParsingError::EndOfFile)?;
+fn read_comment(iterator: &mut SourceChars<'_, impl Read>, start: Span) -> Result<(Token, Span)> {
+    let span = iterator.span();
+    let comment_type = iterator
+        .next()
+        .ok_or_else(|| ParsingError::EndOfFile(span.clone()))?;
     match comment_type {
         Ok('/') => read_line_comment(iterator),
-        Ok('*') => Err(ParsingError::FeatureNotSupported("block comment".into()).into()),
-        Ok(value) => Err(ParsingError::UnexpectedCharacter(value).into()),
+        Ok('*') => {
+            let token = read_block_comment(iterator)?;
+            Ok((token, iterator.span_from(&start)))
+        }
+        Ok(value) => Err(ParsingError::UnexpectedCharacter(value, span).into()),
         Err(error) => Err(error.into()),
     }
 }
 
-fn read_line_comment(iterator: &mut Peekable<Chars<'_, BufReader<impl Read>>>) -> Result<Token> {
+/// Reads a line comment, the span only covers the trimmed comment text
+fn read_line_comment(iterator: &mut SourceChars<'_, impl Read>) -> Result<(Token, Span)> {
+    let mut is_doc = matches!(iterator.peek(), Some(Ok('/')));
+    if is_doc {
+        iterator.next();
+        // Only exactly three slashes make a doc comment, `////` is a regular comment
+        is_doc = !matches!(iterator.peek(), Some(Ok('/')));
+    }
+    while let Some(Ok(char)) = iterator.peek() {
+        if *char == '\n' || !char.is_whitespace() {
+            break;
+        }
+        iterator.next();
+    }
+
+    let start = iterator.span();
     let mut comment = String::new();
     for char in iterator.by_ref() {
         let char = char?;
@@ -70,29 +225,223 @@ fn read_line_comment(iterator: &mut Peekable<Chars<'_, BufReader<impl Read>>>) -
 
         comment.push(char);
     }
-    Ok(Token::LineComment(comment.trim().into()))
+    let comment = comment.trim_end().to_owned();
+    let span = start.offset(0, comment.chars().count());
+
+    if is_doc {
+        Ok((Token::DocComment(comment), span))
+    } else {
+        Ok((Token::LineComment(comment), span))
+    }
 }
 
-fn read_operator(
-    prefix: char,
-    iterator: &mut Peekable<Chars<'_, BufReader<impl Read>>>,
-) -> Result<Token> {
+fn read_block_comment(iterator: &mut SourceChars<'_, impl Read>) -> Result<Token> {
+    let mut comment = String::new();
+    let mut depth = 1;
+    while depth > 0 {
+        let span = iterator.span();
+        let char = iterator
+            .next()
+            .ok_or_else(|| ParsingError::EndOfFile(span.clone()))??;
+        let span = iterator.span();
+        let next = match iterator.peek() {
+            Some(value) => Some(unwrap_char(value, span)?),
+            None => None,
+        };
+        match (char, next) {
+            ('/', Some('*')) => {
+                iterator.next();
+                depth += 1;
+                comment.push_str("/*");
+            }
+            ('*', Some('/')) => {
+                iterator.next();
+                depth -= 1;
+                if depth > 0 {
+                    comment.push_str("*/");
+                }
+            }
+            (char, _) => comment.push(char),
+        }
+    }
+
+    // `/**` opens a doc comment, while `/**/` is just an empty block comment
+    match comment.strip_prefix('*') {
+        Some(documentation) if !documentation.is_empty() => {
+            Ok(Token::DocComment(strip_block_decoration(documentation)))
+        }
+        _ => Ok(Token::BlockComment(comment.trim().into())),
+    }
+}
+
+fn strip_block_decoration(comment: &str) -> String {
+    let lines: Vec<_> = comment
+        .lines()
+        .map(|line| {
+            let line = line.trim();
+            match line.strip_prefix('*') {
+                Some(line) => line.trim(),
+                None => line,
+            }
+        })
+        .collect();
+
+    let start = lines.iter().position(|line| !line.is_empty());
+    let end = lines.iter().rposition(|line| !line.is_empty());
+    match (start, end) {
+        (Some(start), Some(end)) => lines[start..=end].join("\n"),
+        _ => String::new(),
+    }
+}
+
+fn read_operator(prefix: char, iterator: &mut SourceChars<'_, impl Read>) -> Result<Token> {
     let mut operator = String::new();
     operator.push(prefix);
-    let next = iterator.next().ok_or(ParsingError::EndOfFile)?;
-    match next {
-        Ok('>') => {
-            operator.push('>');
-            Ok(Token::Operator(operator))
+    if prefix == '-' && iterator.next_if('>') {
+        operator.push('>');
+        return Ok(Token::Operator(operator));
+    }
+
+    loop {
+        let span = iterator.span();
+        let value = match iterator.peek() {
+            Some(value) => unwrap_char(value, span)?,
+            None => break,
+        };
+        // A comment right after an operator is not a part of it
+        let starts_comment = value == '/' && matches!(iterator.peek_nth(1), Some(Ok('/' | '*')));
+        if !OPERATOR_CHARACTERS.contains(value) || starts_comment {
+            break;
         }
-        Ok(value) => Err(ParsingError::UnexpectedCharacter(value).into()),
-        Err(error) => Err(error.into()),
+        operator.push(value);
+        iterator.next();
+    }
+    Ok(Token::Operator(operator))
+}
+
+/// Reads integer and floating point literals, including hex, binary, octal and `_` separators
+fn read_number(prefix: char, iterator: &mut SourceChars<'_, impl Read>) -> Result<Token> {
+    let mut number = String::new();
+    number.push(prefix);
+    loop {
+        let span = iterator.span();
+        let value = match iterator.peek() {
+            Some(value) => unwrap_char(value, span)?,
+            None => break,
+        };
+        let is_hex = number.starts_with("0x");
+        let exponent = if is_hex { ['p', 'P'] } else { ['e', 'E'] };
+        let is_part = match value {
+            '.' => matches!(iterator.peek_nth(1), Some(Ok(c)) if c.is_ascii_digit()),
+            '+' | '-' => number.ends_with(exponent),
+            value => value.is_alphanumeric() || value == '_',
+        };
+        if !is_part {
+            break;
+        }
+        number.push(value);
+        iterator.next();
+    }
+    Ok(Token::Number(number))
+}
+
+/// Reads `#"..."#` strings, the opening `#` has already been consumed
+fn read_raw_string(iterator: &mut SourceChars<'_, impl Read>) -> Result<Token> {
+    let mut hashes = 1;
+    while iterator.next_if('#') {
+        hashes += 1;
+    }
+    let span = iterator.span();
+    match iterator.next() {
+        Some(Ok('"')) => read_string(iterator, hashes),
+        Some(Ok(value)) => Err(ParsingError::UnexpectedCharacter(value, span).into()),
+        Some(Err(error)) => Err(error.into()),
+        None => Err(ParsingError::EndOfFile(span).into()),
+    }
+}
+
+/// Reads a string literal after its opening quote, keeping its contents as they are written.
+/// `hashes` is the number of `#` delimiting a raw string.
+fn read_string(iterator: &mut SourceChars<'_, impl Read>, hashes: usize) -> Result<Token> {
+    let multiline =
+        matches!(iterator.peek(), Some(Ok('"'))) && matches!(iterator.peek_nth(1), Some(Ok('"')));
+    if multiline {
+        iterator.next();
+        iterator.next();
+    }
+    let quotes = if multiline { 3 } else { 1 };
+
+    let mut value = String::new();
+    loop {
+        let span = iterator.span();
+        let char = iterator
+            .next()
+            .ok_or_else(|| ParsingError::EndOfFile(span.clone()))??;
+        match char {
+            '\n' if !multiline => {
+                return Err(ParsingError::GeneralError("Unterminated string".into(), span).into())
+            }
+            '\\' if is_delimited(iterator, 0, hashes) => {
+                value.push(char);
+                for _ in 0..hashes {
+                    iterator.next();
+                    value.push('#');
+                }
+                if iterator.next_if('(') {
+                    value.push('(');
+                    read_interpolation(iterator, &mut value)?;
+                } else if let Some(escaped) = iterator.next() {
+                    value.push(escaped?);
+                }
+            }
+            '"' if is_closing(iterator, quotes - 1, hashes) => {
+                for _ in 0..quotes - 1 + hashes {
+                    iterator.next();
+                }
+                return Ok(Token::StringLiteral(value));
+            }
+            char => value.push(char),
+        }
+    }
+}
+
+/// Checks whether the next `quotes` characters are quotes, followed by `hashes` hashes
+fn is_closing(iterator: &mut SourceChars<'_, impl Read>, quotes: usize, hashes: usize) -> bool {
+    (0..quotes).all(|i| matches!(iterator.peek_nth(i), Some(Ok('"'))))
+        && is_delimited(iterator, quotes, hashes)
+}
+
+fn is_delimited(iterator: &mut SourceChars<'_, impl Read>, skip: usize, hashes: usize) -> bool {
+    (skip..skip + hashes).all(|i| matches!(iterator.peek_nth(i), Some(Ok('#'))))
+}
+
+/// Copies an interpolated expression up to and including its closing parenthesis
+fn read_interpolation(iterator: &mut SourceChars<'_, impl Read>, value: &mut String) -> Result<()> {
+    let mut depth = 1;
+    while depth > 0 {
+        let span = iterator.span();
+        let char = iterator
+            .next()
+            .ok_or_else(|| ParsingError::EndOfFile(span.clone()))??;
+        match char {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            '"' => {
+                if let Token::StringLiteral(nested) = read_string(iterator, 0)? {
+                    value.push('"');
+                    value.push_str(&nested);
+                }
+            }
+            _ => (),
+        }
+        value.push(char);
     }
+    Ok(())
 }
 
-fn unwrap_char(value: &Result<char, std::io::Error>) -> Result<char> {
+fn unwrap_char(value: &io::Result<char>, span: Span) -> Result<char> {
     match value {
         Ok(value) => Ok(value.to_owned()),
-        Err(error) => Err(ParsingError::GeneralError(format!("{error}")).into()),
+        Err(error) => Err(ParsingError::GeneralError(format!("{error}"), span).into()),
     }
 }
@@ -0,0 +1,224 @@
+use std::fmt::{self, Display, Formatter};
+
+use anyhow::Result;
+
+use crate::{errors::ParsingError, parsing::TokenIter, Token};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TypeRef {
+    /// Possibly qualified and generic type, such as `Foundation.Data` or `Result<User, Error>`
+    Named(Vec<TypeComponent>),
+    Optional(Box<TypeRef>),
+    Array(Box<TypeRef>),
+    Dictionary(Box<TypeRef>, Box<TypeRef>),
+    Tuple(Vec<TupleElement>),
+    /// `some` type
+    Opaque(Box<TypeRef>),
+    /// `any` type
+    Existential(Box<TypeRef>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TypeComponent {
+    pub name: String,
+    pub generics: Vec<TypeRef>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TupleElement {
+    pub label: Option<String>,
+    pub element_type: TypeRef,
+}
+
+impl TypeRef {
+    pub fn named(name: &str) -> TypeRef {
+        TypeRef::Named(vec![TypeComponent {
+            name: name.into(),
+            generics: vec![],
+        }])
+    }
+
+    /// Name of the innermost component for named types, `Data` for `Foundation.Data`
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            TypeRef::Named(components) => components.last().map(|c| c.name.as_str()),
+            _ => None,
+        }
+    }
+}
+
+impl Display for TypeRef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeRef::Named(components) => {
+                for (index, component) in components.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ".")?;
+                    }
+                    write!(f, "{}", component.name)?;
+                    if !component.generics.is_empty() {
+                        write!(f, "<{}>", join(&component.generics))?;
+                    }
+                }
+                Ok(())
+            }
+            TypeRef::Optional(inner) => match inner.as_ref() {
+                TypeRef::Opaque(_) | TypeRef::Existential(_) => write!(f, "({inner})?"),
+                inner => write!(f, "{inner}?"),
+            },
+            TypeRef::Array(element) => write!(f, "[{element}]"),
+            TypeRef::Dictionary(key, value) => write!(f, "[{key}: {value}]"),
+            TypeRef::Tuple(elements) => {
+                let elements: Vec<_> = elements
+                    .iter()
+                    .map(|e| match &e.label {
+                        Some(label) => format!("{label}: {}", e.element_type),
+                        None => e.element_type.to_string(),
+                    })
+                    .collect();
+                write!(f, "({})", elements.join(", "))
+            }
+            TypeRef::Opaque(inner) => write!(f, "some {inner}"),
+            TypeRef::Existential(inner) => write!(f, "any {inner}"),
+        }
+    }
+}
+
+fn join(types: &[TypeRef]) -> String {
+    types
+        .iter()
+        .map(|t| t.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub(crate) fn read_type(tokens: &mut TokenIter) -> Result<TypeRef> {
+    let mut type_ref = match tokens.next() {
+        Some((Token::Identifier(keyword, false), _)) if keyword == "some" => {
+            return Ok(TypeRef::Opaque(Box::new(read_type(tokens)?)))
+        }
+        Some((Token::Identifier(keyword, false), _)) if keyword == "any" => {
+            return Ok(TypeRef::Existential(Box::new(read_type(tokens)?)))
+        }
+        Some((Token::Identifier(name, _), _)) => read_named(name, tokens)?,
+        Some((Token::LeftBracket, _)) => read_collection(tokens)?,
+        Some((Token::LeftParenthesis, _)) => read_tuple(tokens)?,
+        Some((token, span)) => return Err(ParsingError::UnexpectedToken(token, span).into()),
+        None => return Err(tokens.end_of_file().into()),
+    };
+
+    while let Some(Token::QuestionMark) = tokens.peek() {
+        tokens.next();
+        type_ref = TypeRef::Optional(Box::new(type_ref));
+    }
+    Ok(type_ref)
+}
+
+pub(crate) fn read_named(name: String, tokens: &mut TokenIter) -> Result<TypeRef> {
+    let mut components = vec![read_component(name, tokens)?];
+
+    while let Some(Token::Dot) = tokens.peek() {
+        tokens.next();
+        match tokens.next() {
+            Some((Token::Identifier(name, _), _)) => components.push(read_component(name, tokens)?),
+            Some((token, span)) => return Err(ParsingError::UnexpectedToken(token, span).into()),
+            None => return Err(tokens.end_of_file().into()),
+        }
+    }
+    Ok(TypeRef::Named(components))
+}
+
+fn read_component(name: String, tokens: &mut TokenIter) -> Result<TypeComponent> {
+    let mut generics = vec![];
+    if let Some(Token::LeftAngle) = tokens.peek() {
+        tokens.next();
+        loop {
+            generics.push(read_type(tokens)?);
+            match tokens.next() {
+                Some((Token::Comma, _)) => (),
+                Some((Token::RightAngle, _)) => break,
+                Some((token, span)) => {
+                    return Err(ParsingError::UnexpectedToken(token, span).into())
+                }
+                None => return Err(tokens.end_of_file().into()),
+            }
+        }
+    }
+    Ok(TypeComponent { name, generics })
+}
+
+fn read_collection(tokens: &mut TokenIter) -> Result<TypeRef> {
+    let element = read_type(tokens)?;
+    match tokens.next() {
+        Some((Token::RightBracket, _)) => Ok(TypeRef::Array(Box::new(element))),
+        Some((Token::Colon, _)) => {
+            let value = read_type(tokens)?;
+            match tokens.next() {
+                Some((Token::RightBracket, _)) => {
+                    Ok(TypeRef::Dictionary(Box::new(element), Box::new(value)))
+                }
+                Some((token, span)) => Err(ParsingError::UnexpectedToken(token, span).into()),
+                None => Err(tokens.end_of_file().into()),
+            }
+        }
+        Some((token, span)) => Err(ParsingError::UnexpectedToken(token, span).into()),
+        None => Err(tokens.end_of_file().into()),
+    }
+}
+
+fn read_tuple(tokens: &mut TokenIter) -> Result<TypeRef> {
+    let mut elements = vec![];
+    if let Some(Token::RightParenthesis) = tokens.peek() {
+        tokens.next();
+        return Ok(TypeRef::Tuple(elements));
+    }
+
+    loop {
+        elements.push(read_tuple_element(tokens)?);
+        match tokens.next() {
+            Some((Token::Comma, _)) => (),
+            Some((Token::RightParenthesis, _)) => break,
+            Some((token, span)) => return Err(ParsingError::UnexpectedToken(token, span).into()),
+            None => return Err(tokens.end_of_file().into()),
+        }
+    }
+
+    // A single unlabeled element is just a parenthesized type
+    if elements.len() == 1 && elements[0].label.is_none() {
+        return Ok(elements.remove(0).element_type);
+    }
+    Ok(TypeRef::Tuple(elements))
+}
+
+fn read_tuple_element(tokens: &mut TokenIter) -> Result<TupleElement> {
+    let name = match tokens.peek() {
+        Some(Token::Identifier(name, escaped)) if *escaped || (name != "some" && name != "any") => {
+            name.clone()
+        }
+        _ => {
+            return Ok(TupleElement {
+                label: None,
+                element_type: read_type(tokens)?,
+            })
+        }
+    };
+    tokens.next();
+
+    if let Some(Token::Colon) = tokens.peek() {
+        tokens.next();
+        return Ok(TupleElement {
+            label: Some(name),
+            element_type: read_type(tokens)?,
+        });
+    }
+
+    let mut element_type = read_named(name, tokens)?;
+    while let Some(Token::QuestionMark) = tokens.peek() {
+        tokens.next();
+        element_type = TypeRef::Optional(Box::new(element_type));
+    }
+    Ok(TupleElement {
+        label: None,
+        element_type,
+    })
+}